@@ -0,0 +1,16 @@
+use contract::ext_ffi;
+use types::api_error;
+
+// Whether the deploy was given the named argument `name`, for the arguments sessions only
+// pass on when present.
+pub fn has_named_arg(name: &str) -> bool {
+    let mut arg_size: usize = 0;
+    let ret = unsafe {
+        ext_ffi::casper_get_named_arg_size(
+            name.as_bytes().as_ptr(),
+            name.len(),
+            &mut arg_size as *mut usize,
+        )
+    };
+    api_error::result_from(ret).is_ok()
+}
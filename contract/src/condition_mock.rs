@@ -0,0 +1,77 @@
+#![no_main]
+#![no_std]
+
+extern crate alloc;
+
+use alloc::{
+    string::{String, ToString},
+    vec,
+};
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    account::AccountHash,
+    contracts::{EntryPoints, NamedKeys},
+    CLType, CLTyped, CLValue, EntryPoint, EntryPointAccess, EntryPointType, Parameter, URef, U512,
+};
+
+const ALLOWLIST: &str = "allowlist";
+const CONTRACT_HASH_KEY: &str = "condition_contract_hash";
+
+// Stand-in for a compliance contract, such as a KYC allowlist, used by the tests.
+// Only the accounts added with `allow` pass the `can_withdraw` check of the vesting contract.
+// Anyone can call `allow`, so it must not be used outside of tests.
+#[no_mangle]
+pub extern "C" fn can_withdraw() {
+    let recipient: AccountHash = runtime::get_named_arg("recipient");
+    let _amount: U512 = runtime::get_named_arg("amount");
+    let allowed: bool = storage::dictionary_get(get_allowlist(), &recipient.to_string())
+        .unwrap_or_revert()
+        .unwrap_or_default();
+    runtime::ret(CLValue::from_t(allowed).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn allow() {
+    let account: AccountHash = runtime::get_named_arg("account");
+    storage::dictionary_put(get_allowlist(), &account.to_string(), true);
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(EntryPoint::new(
+        "can_withdraw",
+        vec![
+            Parameter::new("recipient", AccountHash::cl_type()),
+            Parameter::new("amount", U512::cl_type()),
+        ],
+        CLType::Bool,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "allow",
+        vec![Parameter::new("account", AccountHash::cl_type())],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    // The dictionary is created in the installer's context, then moved into the contract's.
+    let allowlist = storage::new_dictionary(ALLOWLIST).unwrap_or_revert();
+    runtime::remove_key(ALLOWLIST);
+    let mut named_keys = NamedKeys::new();
+    named_keys.insert(String::from(ALLOWLIST), allowlist.into());
+    let (contract_hash, _version) =
+        storage::new_contract(entry_points, Some(named_keys), None, None);
+    runtime::put_key(CONTRACT_HASH_KEY, contract_hash.into());
+}
+
+fn get_allowlist() -> URef {
+    runtime::get_key(ALLOWLIST)
+        .unwrap_or_revert()
+        .into_uref()
+        .unwrap_or_revert()
+}
@@ -1,27 +1,78 @@
 #![no_main]
 #![no_std]
 
+extern crate alloc;
+
+mod args;
+
+use args::has_named_arg;
 use contract::{
     contract_api::{account, runtime, system},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use types::{runtime_args, ContractHash, RuntimeArgs, URef};
+use types::{runtime_args, ContractHash, RuntimeArgs, URef, U512};
+
+const DEPOSIT_PURSE: &str = "vesting_deposit_purse";
 
 // Session code that executes in the callers context.
-// In this design we use a getter function to fetch a purse from the contract to deposit into.
+// The motes are moved into a deposit purse that is handed to the contract's `deposit` entry point,
+// so the contract never gets access to the `main_purse` of the account.
+// The deposit purse is created on the first deposit and kept under the caller's named keys,
+// to be reused by the next ones. The contract empties it of `amount` on every deposit.
 // Session code REQUIRES an argument to be passed called `amount`,
 // Which is used as a limit to how many motes can be transferred from the `main_purse` of the account.
-// NOTE: creating a new purse costs 2,5 cspr, consider storing and reusing them.
+// An optional `transfer_id` is passed on to the contract, which derives one otherwise.
+// The move into the new purse gets an id of its own, derived from `transfer_id`,
+// so that the id passed is only used by the deposit into the vesting purse.
 #[no_mangle]
 pub extern "C" fn call() {
     let deposit_contract_hash: ContractHash = runtime::get_named_arg("deposit_contract_hash");
-    let amount = runtime::get_named_arg("amount");
-    // Calling the deposit contract to get a URef to the deposit purse associated with the recipient
-    let deposit_purse: URef =
-        runtime::call_contract(deposit_contract_hash, "get_deposit_purse", runtime_args! {});
-    // We transfer the specified amount into the deposit purse of the recipient.
-    // As long as this function call does not fail the transfer of motes happens,
-    // and there is no need to transfer the purse URef back to the contract.
-    system::transfer_from_purse_to_purse(account::get_main_purse(), deposit_purse, amount, None)
-        .unwrap_or_revert();
+    let amount: U512 = runtime::get_named_arg("amount");
+    let transfer_id: Option<u64> = if has_named_arg("transfer_id") {
+        Some(runtime::get_named_arg("transfer_id"))
+    } else {
+        None
+    };
+    let session_transfer_id = transfer_id
+        .map(|transfer_id| derive_session_transfer_id(deposit_contract_hash, transfer_id));
+    let purse = get_or_create_deposit_purse();
+    system::transfer_from_purse_to_purse(
+        account::get_main_purse(),
+        purse,
+        amount,
+        session_transfer_id,
+    )
+    .unwrap_or_revert();
+    // The contract pulls the amount into the vesting purse and records it under the caller.
+    let mut args = runtime_args! {
+        "purse" => purse,
+        "amount" => amount,
+    };
+    if let Some(transfer_id) = transfer_id {
+        args.insert("transfer_id", transfer_id).unwrap_or_revert();
+    }
+    runtime::call_contract::<()>(deposit_contract_hash, "deposit", args);
+}
+
+// Creating a purse costs 2,5 CSPR, so one is kept for all the deposits of the account.
+fn get_or_create_deposit_purse() -> URef {
+    match runtime::get_key(DEPOSIT_PURSE) {
+        Some(purse_key) => purse_key.into_uref().unwrap_or_revert(),
+        None => {
+            let new_purse = system::create_purse();
+            runtime::put_key(DEPOSIT_PURSE, new_purse.into());
+            new_purse
+        }
+    }
+}
+
+// The first 8 bytes, little-endian, of the hash of the contract hash followed by the
+// `transfer_id` of the deposit, little-endian.
+fn derive_session_transfer_id(deposit_contract_hash: ContractHash, transfer_id: u64) -> u64 {
+    let mut bytes = deposit_contract_hash.value().to_vec();
+    bytes.extend_from_slice(&transfer_id.to_le_bytes());
+    let hash = runtime::blake2b(bytes);
+    let mut session_transfer_id = [0u8; 8];
+    session_transfer_id.copy_from_slice(&hash[..8]);
+    u64::from_le_bytes(session_transfer_id)
 }
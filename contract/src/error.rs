@@ -15,6 +15,41 @@ pub enum Error {
     LocalPurseKeyMissing = 11,
     UnexpectedType = 12,
     MissingKey = 13,
+    NotAllowedToDelegate = 14,
+    DelegatorKeyMismatch = 15,
+    FundsDelegated = 16,
+    StakeNotReturned = 17,
+    InvalidClockSource = 18,
+    UnsupportedClockSource = 19,
+    InvalidTimeArgument = 20,
+    TimeOutOfRange = 21,
+    InvalidDripSchedule = 22,
+    NothingToAccelerate = 23,
+    InvalidAccelerationArgument = 24,
+    InvalidMilestones = 25,
+    UnknownMilestone = 26,
+    MilestoneNotPending = 27,
+    NotSupportedWithMilestones = 28,
+    ConditionNotMet = 29,
+    InvalidReleaseDestination = 30,
+    NoPendingReleaseDestination = 31,
+    ReleaseDestinationMismatch = 32,
+    InvalidReleaseAmount = 33,
+    InvalidClaimDeadline = 34,
+    ClaimDeadlineNotReached = 35,
+    Paused = 36,
+    InvalidPauseMode = 37,
+    GrantNotAccepted = 38,
+    NotPendingAcceptance = 39,
+    InvalidGrantId = 40,
+    DuplicateGrantId = 41,
+    InvalidStartTrigger = 42,
+    InvalidState = 43,
+    InvalidDepositAmount = 44,
+    InvalidPayoutSplits = 45,
+    InvalidFee = 46,
+    TooManyFunders = 47,
+    InvalidMetadata = 48,
 }
 
 impl From<Error> for ApiError {
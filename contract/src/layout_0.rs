@@ -0,0 +1,107 @@
+//! Named keys of layout 0, the layout the contract was first released with, one per field.
+//! They are only read by `migrate`, to move the state of an upgraded contract into `VestingState`.
+use contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use types::{account::AccountHash, U512};
+use vesting_types::{
+    time_from_u512, Amount, ClockSource, DripSchedule, Lifecycle, PauseMode, StartTrigger, Time,
+    VestingState,
+};
+
+use crate::{error::Error, get_key};
+
+pub const ADMIN: &str = "admin_account";
+pub const ADMIN_RELEASE_DURATION: &str = "admin_release_duration";
+pub const CLIFF_AMOUNT: &str = "cliff_amount";
+pub const CLIFF_TIMESTAMP: &str = "cliff_timestamp";
+pub const DRIP_AMOUNT: &str = "drip_amount";
+pub const DRIP_DURATION: &str = "drip_duration";
+pub const LAST_PAUSE_TIMESTAMP: &str = "last_pause_timestamp";
+pub const ON_PAUSE_DURATION: &str = "on_pause_duration";
+pub const PAUSE_FLAG: &str = "is_paused";
+pub const RECIPIENT: &str = "recipient_account";
+pub const RELEASED_AMOUNT: &str = "released_amount";
+pub const TOTAL_AMOUNT: &str = "total_amount";
+
+const LAYOUT_0_KEYS: [&str; 12] = [
+    ADMIN,
+    ADMIN_RELEASE_DURATION,
+    CLIFF_AMOUNT,
+    CLIFF_TIMESTAMP,
+    DRIP_AMOUNT,
+    DRIP_DURATION,
+    LAST_PAUSE_TIMESTAMP,
+    ON_PAUSE_DURATION,
+    PAUSE_FLAG,
+    RECIPIENT,
+    RELEASED_AMOUNT,
+    TOTAL_AMOUNT,
+];
+
+pub fn read_state() -> VestingState {
+    let admin: AccountHash = get_key(ADMIN);
+    let recipient: AccountHash = get_key(RECIPIENT);
+    let mut state = VestingState {
+        admin,
+        recipient,
+        cliff_timestamp: get_time(CLIFF_TIMESTAMP),
+        cliff_amount: get_key(CLIFF_AMOUNT),
+        drip_duration: get_time(DRIP_DURATION),
+        drip_amount: get_key(DRIP_AMOUNT),
+        total_amount: get_key(TOTAL_AMOUNT),
+        released_amount: get_key(RELEASED_AMOUNT),
+        admin_release_duration: get_time(ADMIN_RELEASE_DURATION),
+        is_paused: get_key(PAUSE_FLAG),
+        last_pause_timestamp: get_time(LAST_PAUSE_TIMESTAMP),
+        on_pause_duration: get_time(ON_PAUSE_DURATION),
+        recipient_can_delegate: false,
+        rewards_to_admin: false,
+        delegated_amount: Amount::zero(),
+        unbonding_amount: Amount::zero(),
+        rewards_paid: Amount::zero(),
+        clock_source: ClockSource::BlockTime,
+        drip_schedule: DripSchedule::Fixed,
+        rebase_scheduled_amount: Amount::zero(),
+        rebase_vested_amount: Amount::zero(),
+        milestone_count: 0,
+        completed_milestones_amount: Amount::zero(),
+        condition_contract: None,
+        release_destination: None,
+        pending_release_destination: None,
+        reclaimed_amount: Amount::zero(),
+        claim_deadline: None,
+        strict_pause: false,
+        pause_mode: PauseMode::Shifting,
+        forfeited_amount: Amount::zero(),
+        pending_acceptance: false,
+        acceptance_timestamp: None,
+        agreement_hash: None,
+        start_trigger: StartTrigger::Absolute,
+        cliff_offset: 0,
+        start_timestamp: None,
+        lifecycle: Lifecycle::Active,
+        funder_count: 0,
+        deposited_amount: Amount::zero(),
+        payout_split_count: 0,
+        fee_bps: 0,
+        fee_collector: None,
+        fees_paid: Amount::zero(),
+        transfer_count: 0,
+        last_transfer_id: None,
+    };
+    if state.is_paused {
+        state.lifecycle = Lifecycle::Paused;
+    }
+    state
+}
+
+// Times were stored as `U512` milliseconds of block time.
+fn get_time(name: &str) -> Time {
+    let time: U512 = get_key(name);
+    time_from_u512(time).unwrap_or_revert_with(Error::UnexpectedType)
+}
+
+pub fn remove_keys() {
+    for name in LAYOUT_0_KEYS {
+        runtime::remove_key(name);
+    }
+}
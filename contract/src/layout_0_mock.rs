@@ -0,0 +1,79 @@
+#![no_main]
+#![no_std]
+
+extern crate alloc;
+
+use alloc::string::{String, ToString};
+use contract::{
+    contract_api::{account, runtime, storage, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    account::AccountHash,
+    bytesrepr::{FromBytes, ToBytes},
+    contracts::{EntryPoints, NamedKeys},
+    CLTyped, U512,
+};
+
+// Named keys of layout 0, the first release of the contract, as read by `layout_0` in the
+// vesting contract.
+const LAYOUT_0_AMOUNTS: [&str; 4] = [
+    "cliff_amount",
+    "drip_amount",
+    "total_amount",
+    "released_amount",
+];
+const LAYOUT_0_TIMES: [&str; 5] = [
+    "cliff_timestamp",
+    "drip_duration",
+    "admin_release_duration",
+    "last_pause_timestamp",
+    "on_pause_duration",
+];
+
+// Installs a contract with the named keys of layout 0 and a vesting purse holding
+// `total_amount`, so the tests can upgrade it to the current contract and check the migration.
+// Unlike the first release, it keeps the package access key, which the upgrade needs.
+// It has no entry points, the upgrade adds them.
+#[no_mangle]
+pub extern "C" fn call() {
+    let mut named_keys = NamedKeys::new();
+    put_arg::<AccountHash>(&mut named_keys, "admin", "admin_account");
+    put_arg::<AccountHash>(&mut named_keys, "recipient", "recipient_account");
+    for name in LAYOUT_0_AMOUNTS {
+        put_arg::<U512>(&mut named_keys, name, name);
+    }
+    // Layout 0 kept times as `U512` milliseconds.
+    for name in LAYOUT_0_TIMES {
+        let time: u64 = runtime::get_named_arg(&[name, "_ms"].concat());
+        put(&mut named_keys, name, U512::from(time));
+    }
+    put_arg::<bool>(&mut named_keys, "is_paused", "is_paused");
+
+    let total_amount: U512 = runtime::get_named_arg("total_amount");
+    let purse = system::create_purse();
+    system::transfer_from_purse_to_purse(account::get_main_purse(), purse, total_amount, None)
+        .unwrap_or_revert();
+    named_keys.insert(String::from("vesting_main_purse"), purse.into());
+
+    let (contract_hash, _version) = storage::new_contract(
+        EntryPoints::new(),
+        Some(named_keys),
+        Some(String::from("vesting_contract_package_hash")),
+        Some(String::from("vesting_contract_package_access")),
+    );
+    runtime::put_key("vesting_contract", contract_hash.into());
+    runtime::put_key(
+        "vesting_contract_hash",
+        storage::new_uref(contract_hash).into(),
+    );
+}
+
+fn put_arg<T: CLTyped + FromBytes + ToBytes>(named_keys: &mut NamedKeys, arg: &str, name: &str) {
+    let value: T = runtime::get_named_arg(arg);
+    put(named_keys, name, value);
+}
+
+fn put<T: CLTyped + ToBytes>(named_keys: &mut NamedKeys, name: &str, value: T) {
+    named_keys.insert(name.to_string(), storage::new_uref(value).into());
+}
@@ -2,46 +2,105 @@
 
 extern crate alloc;
 mod error;
+mod layout_0;
 use contract::{
-    contract_api::{runtime, storage, system},
+    contract_api::{self, runtime, storage, system},
+    ext_ffi,
     unwrap_or_revert::UnwrapOrRevert,
 };
 use core::cmp;
 use error::Error;
-use std::convert::TryInto;
+use std::{
+    collections::BTreeMap,
+    convert::{TryFrom, TryInto},
+};
 use types::{
     account::AccountHash,
-    bytesrepr::{FromBytes, ToBytes},
-    contracts::{EntryPoints, NamedKeys},
-    CLType, CLTyped, CLValue, EntryPoint, EntryPointAccess, EntryPointType, Parameter, URef, U512,
+    api_error,
+    bytesrepr::{self, FromBytes, ToBytes},
+    contracts::{ContractPackageHash, EntryPoints, NamedKeys},
+    runtime_args,
+    system::{auction, CallStackElement},
+    ApiError, CLType, CLTyped, CLValue, ContractHash, EntryPoint, EntryPointAccess, EntryPointType,
+    EraId, Key, Parameter, PublicKey, RuntimeArgs, URef, U512,
+};
+use vesting_types::{
+    Amount, ClockSource, DripSchedule, GrantInfo, Lifecycle, Milestone, MilestoneStatus, PauseMode,
+    PayoutSplit, ReleaseDestination, StartTrigger, Time, VestingEvent, VestingState,
+    MAX_BASIS_POINTS,
 };
 
-pub type Time = U512;
-pub type Amount = U512;
-
-pub const ADMIN: &str = "admin_account";
-pub const ADMIN_RELEASE_DURATION: &str = "admin_release_duration";
-pub const CLIFF_AMOUNT: &str = "cliff_amount";
-pub const CLIFF_TIMESTAMP: &str = "cliff_timestamp";
-pub const DRIP_AMOUNT: &str = "drip_amount";
-pub const DRIP_DURATION: &str = "drip_duration";
-pub const LAST_PAUSE_TIMESTAMP: &str = "last_pause_timestamp";
-pub const ON_PAUSE_DURATION: &str = "on_pause_duration";
-pub const PAUSE_FLAG: &str = "is_paused";
 pub const PURSE_NAME: &str = "vesting_main_purse";
-pub const RECIPIENT: &str = "recipient_account";
-pub const RELEASED_AMOUNT: &str = "released_amount";
-pub const TOTAL_AMOUNT: &str = "total_amount";
+pub const STATE: &str = "vesting_state";
+pub const LAYOUT_VERSION: &str = "layout_version";
+pub const EVENTS: &str = "events";
+pub const EVENT_COUNT: &str = "event_count";
+pub const GRANT_INFO: &str = "grant_info";
+// Dictionary of the amounts deposited by each funder, keyed by the hex of their account hash.
+pub const DEPOSITS: &str = "deposits";
+// Dictionaries of the lists whose length is kept in the state, keyed by the index of their items.
+pub const FUNDERS: &str = "funders";
+pub const MILESTONES: &str = "milestones";
+pub const PAYOUT_SPLITS: &str = "payout_splits";
+
+pub const CONTRACT_KEY: &str = "vesting_contract";
+pub const CONTRACT_HASH_KEY: &str = "vesting_contract_hash";
+pub const PACKAGE_HASH_KEY: &str = "vesting_contract_package_hash";
+pub const PACKAGE_ACCESS_KEY: &str = "vesting_contract_package_access";
+// Dictionary of the installing account, from grant ids to the contracts installed with them.
+pub const GRANTS_KEY: &str = "vesting_grants";
+
+// Longest grant id, as it is used as a dictionary item key.
+const MAX_GRANT_ID_LENGTH: usize = 64;
+// Most metadata entries, and bytes in their keys and values together.
+const MAX_METADATA_ENTRIES: usize = 16;
+const MAX_METADATA_BYTES: usize = 1024;
+// Most payout splits, as each one costs a transfer on every withdrawal.
+const MAX_PAYOUT_SPLITS: usize = 8;
+// Most funders, as each one costs a refund transfer on every clawback.
+const MAX_FUNDERS: usize = 16;
+
+// Version of the contract's named keys layout, bumped whenever `migrate` has work to do.
+// Layout 0 kept every field under its own named key, see `layout_0`, and had no `layout_version`.
+pub const CURRENT_LAYOUT_VERSION: u8 = 1;
+
+// Sane ranges of time arguments, in milliseconds for block time and in eras for the era id.
+// The lower bound on timestamps catches Unix seconds passed as milliseconds.
+const MIN_TIMESTAMP_MS: Time = 1_000_000_000_000; // 2001-09-09
+const MAX_TIMESTAMP_MS: Time = 32_503_680_000_000; // 3000-01-01
+const MAX_DURATION_MS: Time = 3_155_760_000_000; // 100 years
+const MAX_ERA_ID: Time = 4_383_000; // 1000 years of 2 hour eras
+const MAX_DURATION_ERAS: Time = 438_300; // 100 years of 2 hour eras
+
+// `clock_source` value set aside for block height, which isn't supported.
+const CLOCK_SOURCE_BLOCK_HEIGHT: u8 = 1;
+
+const ACTIVE_OR_PAUSED: &[Lifecycle] = &[Lifecycle::Active, Lifecycle::Paused];
+// Lifecycle states in which funds left in the purse can still be sent out.
+const NOT_COMPLETED: &[Lifecycle] = &[
+    Lifecycle::Pending,
+    Lifecycle::Active,
+    Lifecycle::Paused,
+    Lifecycle::Revoked,
+];
+const CLOSABLE: &[Lifecycle] = &[Lifecycle::Active, Lifecycle::Paused, Lifecycle::Revoked];
+
+enum TimeArg {
+    Timestamp,
+    Duration,
+}
 
 #[no_mangle]
 pub extern "C" fn pause() {
-    verify_admin_account();
+    let mut state = read_state();
+    verify_admin_account(&state);
 
-    let is_paused: bool = get_key(PAUSE_FLAG);
-
-    if !is_paused {
-        set_key(LAST_PAUSE_TIMESTAMP, current_timestamp());
-        set_key(PAUSE_FLAG, true);
+    if !state.is_paused {
+        verify_lifecycle(&state, &[Lifecycle::Active]);
+        state.last_pause_timestamp = current_time(&state);
+        state.is_paused = true;
+        state.lifecycle = Lifecycle::Paused;
+        write_state(state);
     } else {
         runtime::revert(Error::AlreadyPaused);
     }
@@ -49,16 +108,21 @@ pub extern "C" fn pause() {
 
 #[no_mangle]
 pub extern "C" fn unpause() {
-    verify_admin_account();
-
-    let is_paused: bool = get_key(PAUSE_FLAG);
+    let mut state = read_state();
+    verify_admin_account(&state);
 
-    if is_paused {
-        let on_pause_duration: U512 = get_key(ON_PAUSE_DURATION);
-        let last_pause_timestamp: U512 = get_key(LAST_PAUSE_TIMESTAMP);
-        let elapsed_timestamp: U512 = current_timestamp() - last_pause_timestamp;
-        set_key(ON_PAUSE_DURATION, on_pause_duration + elapsed_timestamp);
-        set_key(PAUSE_FLAG, false);
+    if state.is_paused {
+        verify_lifecycle(&state, &[Lifecycle::Paused]);
+        let now = current_time(&state);
+        let forfeited_amount = state.end_pause(now);
+        state.lifecycle = Lifecycle::Active;
+        write_state(state);
+        if !forfeited_amount.is_zero() {
+            emit_event(VestingEvent::Forfeited {
+                amount: forfeited_amount,
+                timestamp: now,
+            });
+        }
     } else {
         runtime::revert(Error::AlreadyUnpaused);
     }
@@ -66,125 +130,946 @@ pub extern "C" fn unpause() {
 
 #[no_mangle]
 pub extern "C" fn withdraw() {
-    verify_recipient_account();
+    let mut state = read_state();
+    verify_recipient_account(&state);
+    if state.pending_acceptance {
+        runtime::revert(Error::GrantNotAccepted);
+    }
+    verify_lifecycle(&state, ACTIVE_OR_PAUSED);
+    if state.strict_pause && state.is_paused {
+        runtime::revert(Error::Paused);
+    }
 
     let amount: U512 = runtime::get_named_arg("amount");
-    let available_amount = available_amount();
+    let now = current_time(&state);
+    let available_amount = state.available_amount(now);
     if available_amount < amount {
         runtime::revert(Error::NotEnoughBalance);
+    } else if state.liquid_amount() < amount {
+        runtime::revert(Error::FundsDelegated);
+    } else if !withdrawal_allowed(&state, amount) {
+        runtime::revert(Error::ConditionNotMet);
     } else {
-        let released_amount: U512 = get_key(RELEASED_AMOUNT);
-        set_key(RELEASED_AMOUNT, released_amount + amount);
-        transfer_out_clx_to_caller(PURSE_NAME, amount);
+        state.released_amount += amount;
+        let fee = charge_fee(&mut state, amount);
+        let payout_amount = amount - fee.amount;
+        let payout_splits: Vec<PayoutSplit> = read_list(PAYOUT_SPLITS, state.payout_split_count);
+        let mut transfer_ids = next_transfer_ids(
+            &mut state,
+            get_optional_named_arg("transfer_id"),
+            payout_remainder(&payout_splits, payout_amount),
+        );
+        write_state(state);
+        pay_fee(fee, now, &mut transfer_ids);
+        pay_out(&payout_splits, payout_amount, &mut transfer_ids);
+        runtime::ret(CLValue::from_t(amount).unwrap_or_revert());
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn set_payout_splits() {
+    let mut state = read_state();
+    verify_recipient_account(&state);
+    verify_open(&state);
+
+    let payout_splits = get_payout_splits_arg(&state);
+    write_list(PAYOUT_SPLITS, &payout_splits);
+    state.payout_split_count = payout_splits.len() as u32;
+    let now = current_time(&state);
+    write_state(state);
+    emit_event(VestingEvent::PayoutSplitsChanged {
+        payout_splits,
+        timestamp: now,
+    });
+}
+
+// Asks the condition contract, if any, whether the recipient can withdraw `amount`.
+fn withdrawal_allowed(state: &VestingState, amount: U512) -> bool {
+    match state.condition_contract {
+        Some(condition_contract) => runtime::call_contract(
+            condition_contract,
+            "can_withdraw",
+            runtime_args! {
+                "recipient" => state.recipient,
+                "amount" => amount
+            },
+        ),
+        None => true,
     }
 }
 
 #[no_mangle]
 pub extern "C" fn admin_release() {
-    verify_admin_account();
+    let mut state = read_state();
+    verify_admin_account(&state);
+    verify_lifecycle(&state, ACTIVE_OR_PAUSED);
 
-    let is_paused: bool = get_key(PAUSE_FLAG);
-    if !is_paused {
+    let now = current_time(&state);
+    let milestones: Vec<Milestone> = read_list(MILESTONES, state.milestone_count);
+    let has_expired_milestones = milestones.iter().any(|milestone| milestone.is_expired(now));
+    let release_eligible =
+        state.is_paused && now - state.last_pause_timestamp >= state.admin_release_duration;
+    if !release_eligible && has_expired_milestones {
+        let amount = reclaim_expired_milestones(&mut state, milestones, now);
+        let fee = charge_fee(&mut state, amount);
+        let refund_amount = amount - fee.amount;
+        let destination = state.release_destination;
+        let shares = funder_shares(&state, refund_amount);
+        let mut transfer_ids = next_transfer_ids(
+            &mut state,
+            get_optional_named_arg("transfer_id"),
+            refund_remainder(&shares, refund_amount),
+        );
+        write_state(state);
+        pay_fee(fee, now, &mut transfer_ids);
+        refund(shares, destination, refund_amount, now, &mut transfer_ids);
+        runtime::ret(CLValue::from_t(amount).unwrap_or_revert());
+    }
+
+    if !state.is_paused {
         runtime::revert(Error::NotPaused);
     }
-    let last_pause_timestamp: U512 = get_key(LAST_PAUSE_TIMESTAMP);
-    let since_last_pause = current_timestamp() - last_pause_timestamp;
-    let required_wait_duration: U512 = get_key(ADMIN_RELEASE_DURATION);
-    if since_last_pause < required_wait_duration {
+    if !release_eligible {
         runtime::revert(Error::NotEnoughTimeElapsed);
     }
-    let total_amount: U512 = get_key(TOTAL_AMOUNT);
-    let released_amount: U512 = get_key(RELEASED_AMOUNT);
-    if total_amount == released_amount {
+    if let Some(amount) = get_optional_named_arg::<U512>("amount") {
+        reclaim_unvested(state, now, amount);
+    }
+    if state.remaining_amount().is_zero() {
         runtime::revert(Error::NothingToWithdraw);
     }
-    let amount_to_withdraw = total_amount - released_amount;
-    set_key(RELEASED_AMOUNT, total_amount);
-    transfer_out_clx_to_caller(PURSE_NAME, amount_to_withdraw);
+    if state.liquid_amount() != state.remaining_amount() {
+        runtime::revert(Error::FundsDelegated);
+    }
+    let amount_to_withdraw = state.release_remaining();
+    state.lifecycle = Lifecycle::Revoked;
+    let fee = charge_fee(&mut state, amount_to_withdraw);
+    let refund_amount = amount_to_withdraw - fee.amount;
+    let destination = state.release_destination;
+    let shares = funder_shares(&state, refund_amount);
+    let mut transfer_ids = next_transfer_ids(
+        &mut state,
+        get_optional_named_arg("transfer_id"),
+        refund_remainder(&shares, refund_amount),
+    );
+    write_state(state);
+    pay_fee(fee, now, &mut transfer_ids);
+    refund(shares, destination, refund_amount, now, &mut transfer_ids);
+    runtime::ret(CLValue::from_t(amount_to_withdraw).unwrap_or_revert());
+}
+
+// Takes `amount` of the unvested balance back, and scales the rest of the schedule down
+// so that the recipient's remaining drips shrink proportionally.
+fn reclaim_unvested(mut state: VestingState, now: Time, amount: U512) -> ! {
+    if state.has_milestones() {
+        runtime::revert(Error::NotSupportedWithMilestones);
+    }
+    let vested_amount = state.vested_amount(now);
+    let unvested_amount = state.granted_amount() - vested_amount;
+    if amount.is_zero() || amount > unvested_amount {
+        runtime::revert(Error::InvalidReleaseAmount);
+    }
+    if state.liquid_amount() < amount {
+        runtime::revert(Error::FundsDelegated);
+    }
+    state.rebase(now, vested_amount);
+    state.reclaimed_amount += amount;
+    let fee = charge_fee(&mut state, amount);
+    let refund_amount = amount - fee.amount;
+    let destination = state.release_destination;
+    let shares = funder_shares(&state, refund_amount);
+    let mut transfer_ids = next_transfer_ids(
+        &mut state,
+        get_optional_named_arg("transfer_id"),
+        refund_remainder(&shares, refund_amount),
+    );
+    write_state(state);
+    emit_event(VestingEvent::Reclaimed {
+        amount,
+        timestamp: now,
+    });
+    pay_fee(fee, now, &mut transfer_ids);
+    refund(shares, destination, refund_amount, now, &mut transfer_ids);
+    runtime::ret(CLValue::from_t(amount).unwrap_or_revert())
+}
+
+// Takes the expired milestones out of the grant, and returns the amount to send back.
+fn reclaim_expired_milestones(
+    state: &mut VestingState,
+    milestones: Vec<Milestone>,
+    now: Time,
+) -> Amount {
+    let mut total_reclaimed = Amount::zero();
+    for (id, mut milestone) in milestones.into_iter().enumerate() {
+        if !milestone.is_expired(now) {
+            continue;
+        }
+        milestone.status = MilestoneStatus::Reclaimed;
+        total_reclaimed += milestone.amount;
+        emit_event(VestingEvent::MilestoneReclaimed {
+            id: id as u32,
+            amount: milestone.amount,
+            timestamp: now,
+        });
+        write_list_item(MILESTONES, id as u32, milestone);
+    }
+    if state.liquid_amount() < total_reclaimed {
+        runtime::revert(Error::FundsDelegated);
+    }
+    state.reclaimed_amount += total_reclaimed;
+    total_reclaimed
 }
 
+#[no_mangle]
+pub extern "C" fn reclaim_forfeited() {
+    let mut state = read_state();
+    verify_admin_account(&state);
+    verify_lifecycle(&state, ACTIVE_OR_PAUSED);
+
+    let amount = state.forfeited_amount;
+    if amount.is_zero() {
+        runtime::revert(Error::NothingToWithdraw);
+    }
+    if state.liquid_amount() < amount {
+        runtime::revert(Error::FundsDelegated);
+    }
+    state.forfeited_amount = Amount::zero();
+    state.reclaimed_amount += amount;
+    let now = current_time(&state);
+    let destination = state.release_destination;
+    let shares = funder_shares(&state, amount);
+    let mut transfer_ids = next_transfer_ids(&mut state, None, refund_remainder(&shares, amount));
+    write_state(state);
+    emit_event(VestingEvent::Reclaimed {
+        amount,
+        timestamp: now,
+    });
+    refund(shares, destination, amount, now, &mut transfer_ids);
+    runtime::ret(CLValue::from_t(amount).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn sweep_expired() {
+    let mut state = read_state();
+    verify_admin_account(&state);
+    verify_lifecycle(&state, ACTIVE_OR_PAUSED);
+
+    let now = current_time(&state);
+    let claim_deadline = state
+        .adjusted_claim_deadline(now)
+        .unwrap_or_revert_with(Error::ClaimDeadlineNotReached);
+    if now < claim_deadline {
+        runtime::revert(Error::ClaimDeadlineNotReached);
+    }
+    if state.remaining_amount().is_zero() {
+        runtime::revert(Error::NothingToWithdraw);
+    }
+    if state.liquid_amount() != state.remaining_amount() {
+        runtime::revert(Error::FundsDelegated);
+    }
+    let amount = state.release_remaining();
+    state.lifecycle = Lifecycle::Revoked;
+    let destination = state.release_destination;
+    let shares = funder_shares(&state, amount);
+    let mut transfer_ids = next_transfer_ids(&mut state, None, refund_remainder(&shares, amount));
+    write_state(state);
+    emit_event(VestingEvent::Swept {
+        amount,
+        timestamp: now,
+    });
+    refund(shares, destination, amount, now, &mut transfer_ids);
+    runtime::ret(CLValue::from_t(amount).unwrap_or_revert());
+}
+
+// Sends back what was deposited on top of the grant, refunded to the funders in proportion to
+// their deposits, which are debited by their shares.
+#[no_mangle]
+pub extern "C" fn sweep_excess() {
+    let mut state = read_state();
+    verify_admin_account(&state);
+    verify_lifecycle(&state, NOT_COMPLETED);
+
+    // Unbonded stake may be back in the purse with its rewards before it is settled.
+    if !state.unbonding_amount.is_zero() {
+        runtime::revert(Error::FundsDelegated);
+    }
+    let vesting_purse = get_purse(PURSE_NAME);
+    let balance =
+        system::get_purse_balance(vesting_purse).unwrap_or_revert_with(Error::LocalPurseKeyMissing);
+    if balance <= state.liquid_amount() {
+        runtime::revert(Error::NothingToWithdraw);
+    }
+    let amount = balance - state.liquid_amount();
+    let shares = funder_shares(&state, amount);
+    debit_deposits(&mut state, amount);
+    let destination = state.release_destination;
+    let now = current_time(&state);
+    let mut transfer_ids = next_transfer_ids(&mut state, None, refund_remainder(&shares, amount));
+    write_state(state);
+    emit_event(VestingEvent::ExcessSwept {
+        amount,
+        timestamp: now,
+    });
+    refund(shares, destination, amount, now, &mut transfer_ids);
+    runtime::ret(CLValue::from_t(amount).unwrap_or_revert());
+}
+
+// Marks a grant with nothing left to vest or withdraw as completed, and sends whatever is
+// still in the purse, such as rewards paid after the last withdrawal, to the release destination.
+// A revoked grant stays revoked, closing it only sends the dust out.
+#[no_mangle]
+pub extern "C" fn close() {
+    let mut state = read_state();
+    verify_admin_account(&state);
+    verify_lifecycle(&state, CLOSABLE);
+
+    let finished = state.remaining_amount().is_zero()
+        && state.delegated_amount.is_zero()
+        && state.unbonding_amount.is_zero();
+    if !finished {
+        runtime::revert(Error::InvalidState);
+    }
+    let vesting_purse = get_purse(PURSE_NAME);
+    let dust =
+        system::get_purse_balance(vesting_purse).unwrap_or_revert_with(Error::LocalPurseKeyMissing);
+    if state.lifecycle != Lifecycle::Revoked {
+        state.lifecycle = Lifecycle::Completed;
+    }
+    state.is_paused = false;
+    let now = current_time(&state);
+    let destination = state.release_destination;
+    let transfer_id = if dust.is_zero() {
+        None
+    } else {
+        Some(next_transfer_ids(&mut state, None, dust).main)
+    };
+    write_state(state);
+    emit_event(VestingEvent::Closed {
+        dust,
+        timestamp: now,
+    });
+    if let Some(transfer_id) = transfer_id {
+        transfer_out_to_release_destination(destination, dust, transfer_id);
+    }
+    runtime::ret(CLValue::from_t(dust).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn propose_release_destination() {
+    let mut state = read_state();
+    verify_admin_account(&state);
+    verify_open(&state);
+
+    let destination =
+        get_release_destination_arg().unwrap_or_revert_with(Error::InvalidReleaseDestination);
+    state.pending_release_destination = Some(destination);
+    let now = current_time(&state);
+    write_state(state);
+    emit_event(VestingEvent::ReleaseDestinationProposed {
+        destination,
+        timestamp: now,
+    });
+}
+
+// Second step of a release destination change. The proposed destination is passed again,
+// so that a mistaken or unexpected proposal is not confirmed blindly.
+#[no_mangle]
+pub extern "C" fn confirm_release_destination() {
+    let mut state = read_state();
+    verify_admin_account(&state);
+    verify_open(&state);
+
+    let pending_destination = state
+        .pending_release_destination
+        .unwrap_or_revert_with(Error::NoPendingReleaseDestination);
+    let destination =
+        get_release_destination_arg().unwrap_or_revert_with(Error::InvalidReleaseDestination);
+    if destination != pending_destination {
+        runtime::revert(Error::ReleaseDestinationMismatch);
+    }
+    state.release_destination = Some(destination);
+    state.pending_release_destination = None;
+    let now = current_time(&state);
+    write_state(state);
+    emit_event(VestingEvent::ReleaseDestinationChanged {
+        destination,
+        timestamp: now,
+    });
+}
+
+#[no_mangle]
+pub extern "C" fn accept_grant() {
+    let mut state = read_state();
+    verify_recipient_account(&state);
+    verify_lifecycle(&state, &[Lifecycle::Pending]);
+
+    if !state.pending_acceptance {
+        runtime::revert(Error::NotPendingAcceptance);
+    }
+    let agreement_hash: Option<[u8; 32]> = get_optional_named_arg("agreement_hash");
+    let now = current_time(&state);
+    state.pending_acceptance = false;
+    state.acceptance_timestamp = Some(now);
+    state.agreement_hash = agreement_hash;
+    if state.start_trigger == StartTrigger::Acceptance {
+        state.start(now);
+    }
+    state.activate_if_ready();
+    write_state(state);
+    emit_event(VestingEvent::GrantAccepted {
+        agreement_hash,
+        timestamp: now,
+    });
+}
+
+// Takes the whole grant back before the recipient accepted it. The grant is no longer
+// pending afterwards, so it can't be accepted anymore.
+#[no_mangle]
+pub extern "C" fn cancel_grant() {
+    let mut state = read_state();
+    verify_admin_account(&state);
+    verify_lifecycle(&state, &[Lifecycle::Pending]);
+
+    if !state.pending_acceptance {
+        runtime::revert(Error::NotPendingAcceptance);
+    }
+    let amount = state.remaining_amount();
+    if state.liquid_amount() != amount {
+        runtime::revert(Error::FundsDelegated);
+    }
+    state.reclaimed_amount += amount;
+    state.pending_acceptance = false;
+    state.lifecycle = Lifecycle::Revoked;
+    // The grant may not be funded yet, or only in part, so only what the purse holds goes back.
+    let balance = match runtime::get_key(PURSE_NAME) {
+        Some(_) => system::get_purse_balance(get_purse(PURSE_NAME))
+            .unwrap_or_revert_with(Error::LocalPurseKeyMissing),
+        None => U512::zero(),
+    };
+    let refund_amount = cmp::min(balance, amount);
+    let now = current_time(&state);
+    let destination = state.release_destination;
+    // Shares of the whole grant, so that every funder gets back all they deposited.
+    let shares = funder_shares(&state, amount);
+    let transfer_ids = if refund_amount.is_zero() {
+        None
+    } else {
+        let main_amount = refund_remainder(&shares, refund_amount);
+        Some(next_transfer_ids(&mut state, None, main_amount))
+    };
+    write_state(state);
+    emit_event(VestingEvent::GrantCancelled {
+        amount,
+        timestamp: now,
+    });
+    if let Some(mut transfer_ids) = transfer_ids {
+        refund(shares, destination, refund_amount, now, &mut transfer_ids);
+    }
+    runtime::ret(CLValue::from_t(amount).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn complete_milestone() {
+    let mut state = read_state();
+    verify_admin_account(&state);
+    verify_open(&state);
+
+    let id: u32 = runtime::get_named_arg("id");
+    if id >= state.milestone_count {
+        runtime::revert(Error::UnknownMilestone);
+    }
+    let mut milestone: Milestone = read_list_item(MILESTONES, id);
+    if milestone.status != MilestoneStatus::Pending {
+        runtime::revert(Error::MilestoneNotPending);
+    }
+    milestone.status = MilestoneStatus::Completed;
+    state.completed_milestones_amount += milestone.amount;
+    write_list_item(MILESTONES, id, milestone);
+    let now = current_time(&state);
+    write_state(state);
+    emit_event(VestingEvent::MilestoneCompleted { id, timestamp: now });
+}
+
+#[no_mangle]
+pub extern "C" fn accelerate() {
+    let mut state = read_state();
+    verify_admin_account(&state);
+    verify_open(&state);
+
+    // Milestone grants are accelerated by completing milestones.
+    if state.has_milestones() {
+        runtime::revert(Error::NotSupportedWithMilestones);
+    }
+    let now = current_time(&state);
+    let vested_amount = state.vested_amount(now);
+    let unvested_amount = state.granted_amount() - vested_amount;
+    if unvested_amount.is_zero() {
+        runtime::revert(Error::NothingToAccelerate);
+    }
+    let amount = match (
+        get_optional_named_arg::<U512>("amount"),
+        get_optional_named_arg::<u8>("percentage"),
+    ) {
+        (Some(amount), None) => amount,
+        (None, Some(percentage)) if percentage <= 100 => {
+            unvested_amount * Amount::from(percentage) / Amount::from(100)
+        }
+        _ => runtime::revert(Error::InvalidAccelerationArgument),
+    };
+    if amount.is_zero() || amount > unvested_amount {
+        runtime::revert(Error::InvalidAccelerationArgument);
+    }
+    state.rebase(now, vested_amount + amount);
+    write_state(state);
+    emit_event(VestingEvent::Accelerated {
+        amount,
+        timestamp: now,
+    });
+    runtime::ret(CLValue::from_t(amount).unwrap_or_revert());
+}
+
+// Motes sent straight into the vesting purse aren't recorded as deposits, so they go to the
+// release destination on clawbacks. Only add access is handed out, so the purse can't be drained.
 #[no_mangle]
 pub extern "C" fn get_deposit_purse() {
-    verify_admin_account();
+    let state = read_state();
+    verify_admin_account(&state);
+    verify_open(&state);
 
-    let vesting_purse = match runtime::get_key(PURSE_NAME) {
-        Some(purse_key) => purse_key.into_uref().unwrap_or_revert(),
-        None => {
-            let new_purse = system::create_purse();
-            runtime::put_key(PURSE_NAME, new_purse.into());
-            new_purse
+    let vesting_purse = get_or_create_vesting_purse();
+    runtime::ret(CLValue::from_t(vesting_purse.into_add()).unwrap_or_revert());
+}
+
+// Moves `amount` from `purse`, filled by the deposit session, into the vesting purse and
+// credits it to the calling account, so clawbacks can be refunded to it later.
+// A schedule waiting for its funding starts once the vesting purse holds the whole grant.
+#[no_mangle]
+pub extern "C" fn deposit() {
+    let mut state = read_state();
+    verify_open(&state);
+
+    let source: URef = runtime::get_named_arg("purse");
+    let amount: U512 = runtime::get_named_arg("amount");
+    if amount.is_zero() {
+        runtime::revert(Error::InvalidDepositAmount);
+    }
+    let funder = runtime::get_caller();
+    let deposited = get_deposit(&funder);
+    if deposited.is_zero() {
+        if state.funder_count as usize >= MAX_FUNDERS {
+            runtime::revert(Error::TooManyFunders);
         }
+        write_list_item(FUNDERS, state.funder_count, funder);
+        state.funder_count += 1;
+    }
+    let vesting_purse = get_or_create_vesting_purse();
+    let transfer_id =
+        next_transfer_ids(&mut state, get_optional_named_arg("transfer_id"), amount).main;
+    system::transfer_from_purse_to_purse(source, vesting_purse, amount, Some(transfer_id))
+        .unwrap_or_revert_with(Error::PurseTransferErr);
+
+    let deposits = get_or_create_dictionary(DEPOSITS);
+    storage::dictionary_put(deposits, &funder.to_string(), deposited + amount);
+    state.deposited_amount += amount;
+
+    if state.start_trigger == StartTrigger::Funding && state.is_start_pending() {
+        let balance = system::get_purse_balance(vesting_purse)
+            .unwrap_or_revert_with(Error::LocalPurseKeyMissing);
+        if balance >= state.liquid_amount() {
+            let now = current_time(&state);
+            state.start(now);
+            state.activate_if_ready();
+        }
+    }
+    write_state(state);
+}
+
+#[no_mangle]
+pub extern "C" fn get_deposited_amount() {
+    let funder: AccountHash = runtime::get_named_arg("funder");
+    runtime::ret(CLValue::from_t(get_deposit(&funder)).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn get_fees_paid() {
+    runtime::ret(CLValue::from_t(read_state().fees_paid).unwrap_or_revert());
+}
+
+// Grants installed before terms and metadata were stored have none.
+#[no_mangle]
+pub extern "C" fn get_grant_info() {
+    let grant_info = if runtime::has_key(GRANT_INFO) {
+        get_key(GRANT_INFO)
+    } else {
+        GrantInfo::default()
+    };
+    runtime::ret(CLValue::from_t(grant_info).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn delegate() {
+    let mut state = read_state();
+    verify_delegator_account(&state);
+    verify_open(&state);
+
+    let delegator: PublicKey = runtime::get_named_arg(auction::ARG_DELEGATOR);
+    let validator: PublicKey = runtime::get_named_arg(auction::ARG_VALIDATOR);
+    let amount: U512 = runtime::get_named_arg(auction::ARG_AMOUNT);
+    verify_delegator_key(&delegator);
+    if state.delegable_amount(current_time(&state)) < amount {
+        runtime::revert(Error::NotEnoughBalance);
+    }
+    state.delegated_amount += amount;
+    let transfer_id = next_transfer_ids(&mut state, None, amount).main;
+    write_state(state);
+    // The auction bonds motes from the delegator's main purse,
+    // so they are moved there first within the same deploy.
+    transfer_out_clx_to_caller(PURSE_NAME, amount, transfer_id);
+    let _: U512 = runtime::call_contract(
+        system::get_auction(),
+        auction::METHOD_DELEGATE,
+        runtime_args! {
+            auction::ARG_DELEGATOR => delegator,
+            auction::ARG_VALIDATOR => validator,
+            auction::ARG_AMOUNT => amount,
+        },
+    );
+}
+
+#[no_mangle]
+pub extern "C" fn undelegate() {
+    let mut state = read_state();
+    verify_delegator_account(&state);
+    verify_open(&state);
+
+    let delegator: PublicKey = runtime::get_named_arg(auction::ARG_DELEGATOR);
+    let validator: PublicKey = runtime::get_named_arg(auction::ARG_VALIDATOR);
+    let amount: U512 = runtime::get_named_arg(auction::ARG_AMOUNT);
+    verify_delegator_key(&delegator);
+    // Anything undelegated above the bonded principal are accrued rewards,
+    // which are paid out when the stake is settled.
+    let principal = cmp::min(amount, state.delegated_amount);
+    state.delegated_amount -= principal;
+    state.unbonding_amount += principal;
+    write_state(state);
+    let _: U512 = runtime::call_contract(
+        system::get_auction(),
+        auction::METHOD_UNDELEGATE,
+        runtime_args! {
+            auction::ARG_DELEGATOR => delegator,
+            auction::ARG_VALIDATOR => validator,
+            auction::ARG_AMOUNT => amount,
+        },
+    );
+}
+
+#[no_mangle]
+pub extern "C" fn get_stake_return_purse() {
+    let state = read_state();
+    verify_delegator_account(&state);
+    verify_open(&state);
+
+    let vesting_purse = get_purse(PURSE_NAME);
+    runtime::ret(CLValue::from_t(vesting_purse.into_add()).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn settle_unbonded() {
+    let mut state = read_state();
+    verify_delegator_account(&state);
+    verify_open(&state);
+
+    let amount: U512 = runtime::get_named_arg("amount");
+    let vesting_purse = get_purse(PURSE_NAME);
+    let balance =
+        system::get_purse_balance(vesting_purse).unwrap_or_revert_with(Error::LocalPurseKeyMissing);
+    if balance < state.liquid_amount() + amount {
+        runtime::revert(Error::StakeNotReturned);
+    }
+    let principal = cmp::min(amount, state.unbonding_amount);
+    state.unbonding_amount -= principal;
+    let rewards = amount - principal;
+    let rewards_account = if state.rewards_to_admin {
+        state.admin
+    } else {
+        state.recipient
+    };
+    state.rewards_paid += rewards;
+    let transfer_id = if rewards.is_zero() {
+        None
+    } else {
+        Some(next_transfer_ids(&mut state, None, rewards).main)
+    };
+    write_state(state);
+    if let Some(transfer_id) = transfer_id {
+        system::transfer_from_purse_to_account(
+            vesting_purse,
+            rewards_account,
+            rewards,
+            Some(transfer_id),
+        )
+        .unwrap_or_revert_with(Error::PurseTransferErr);
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn migrate() {
+    let layout_version: u8 = if runtime::has_key(LAYOUT_VERSION) {
+        get_key(LAYOUT_VERSION)
+    } else {
+        0
     };
-    runtime::ret(CLValue::from_t(vesting_purse).unwrap_or_revert());
+    if layout_version >= CURRENT_LAYOUT_VERSION {
+        verify_admin_account(&read_state());
+        return;
+    }
+    let state = layout_0::read_state();
+    verify_admin_account(&state);
+    layout_0::remove_keys();
+    set_key(STATE, state);
+    set_key(LAYOUT_VERSION, CURRENT_LAYOUT_VERSION);
 }
 
 #[no_mangle]
 pub extern "C" fn call() {
-    let admin: AccountHash = runtime::get_named_arg("admin");
-    let recipient: AccountHash = runtime::get_named_arg("recipient");
-    let cliff_amount: U512 = runtime::get_named_arg("cliff_amount");
-    let cliff_timestamp: U512 = runtime::get_named_arg("cliff_timestamp");
-    let drip_duration: U512 = runtime::get_named_arg("drip_duration");
-    let drip_amount: U512 = runtime::get_named_arg("drip_amount");
-    let total_amount: U512 = runtime::get_named_arg("total_amount");
-    let admin_release_duration: U512 = runtime::get_named_arg("admin_release_duration");
+    // The install session doubles as the upgrade session,
+    // as a new contract version has to be added by the wasm that implements it.
+    let upgrade: bool = get_optional_named_arg("upgrade").unwrap_or_default();
+    if upgrade {
+        upgrade_contract();
+    } else {
+        install_contract();
+    }
+}
+
+fn install_contract() {
+    let clock_source: u8 = get_optional_named_arg("clock_source").unwrap_or_default();
+    // Block height isn't exposed to contracts by the host.
+    if clock_source == CLOCK_SOURCE_BLOCK_HEIGHT {
+        runtime::revert(Error::UnsupportedClockSource);
+    }
+    let clock_source =
+        ClockSource::try_from(clock_source).unwrap_or_revert_with(Error::InvalidClockSource);
+    let drip_schedule: u8 = get_optional_named_arg("drip_schedule").unwrap_or_default();
+    let drip_schedule =
+        DripSchedule::try_from(drip_schedule).unwrap_or_revert_with(Error::InvalidDripSchedule);
+    // Calendar months are computed from UTC dates, which only the block time gives.
+    if drip_schedule.is_calendar() && clock_source != ClockSource::BlockTime {
+        runtime::revert(Error::InvalidDripSchedule);
+    }
+    let pause_mode: u8 = get_optional_named_arg("pause_mode").unwrap_or_default();
+    let pause_mode = PauseMode::try_from(pause_mode).unwrap_or_revert_with(Error::InvalidPauseMode);
+    let total_amount: Amount = runtime::get_named_arg("total_amount");
+    let milestones = get_milestones_arg(clock_source);
+    let has_milestones = !milestones.is_empty();
+    let requires_acceptance: bool =
+        get_optional_named_arg("requires_acceptance").unwrap_or_default();
+    let start_trigger: u8 = get_optional_named_arg("start_trigger").unwrap_or_default();
+    let start_trigger =
+        StartTrigger::try_from(start_trigger).unwrap_or_revert_with(Error::InvalidStartTrigger);
+    if (start_trigger != StartTrigger::Absolute && has_milestones)
+        || (start_trigger == StartTrigger::Acceptance && !requires_acceptance)
+    {
+        runtime::revert(Error::InvalidStartTrigger);
+    }
+    if has_milestones {
+        let milestones_amount = milestones
+            .iter()
+            .fold(Amount::zero(), |sum, milestone| sum + milestone.amount);
+        if milestones_amount != total_amount || drip_schedule != DripSchedule::Fixed {
+            runtime::revert(Error::InvalidMilestones);
+        }
+    }
+    // Milestone grants have neither a cliff nor drips.
+    let (cliff_timestamp, cliff_amount, drip_duration, drip_amount) = if has_milestones {
+        (0, Amount::zero(), 0, Amount::zero())
+    } else {
+        (
+            // A relative cliff is fixed when the start trigger happens.
+            if start_trigger == StartTrigger::Absolute {
+                get_time_arg("cliff_timestamp", TimeArg::Timestamp, clock_source)
+            } else {
+                0
+            },
+            runtime::get_named_arg("cliff_amount"),
+            if drip_schedule.is_calendar() {
+                0
+            } else {
+                get_time_arg("drip_duration", TimeArg::Duration, clock_source)
+            },
+            runtime::get_named_arg("drip_amount"),
+        )
+    };
+    let cliff_offset = if start_trigger == StartTrigger::Absolute {
+        0
+    } else {
+        get_time_arg("cliff_offset", TimeArg::Duration, clock_source)
+    };
+    let mut state = VestingState {
+        admin: runtime::get_named_arg("admin"),
+        recipient: runtime::get_named_arg("recipient"),
+        cliff_timestamp,
+        cliff_amount,
+        drip_duration,
+        drip_amount,
+        total_amount,
+        released_amount: Amount::zero(),
+        admin_release_duration: get_time_arg(
+            "admin_release_duration",
+            TimeArg::Duration,
+            clock_source,
+        ),
+        is_paused: false,
+        last_pause_timestamp: 0,
+        on_pause_duration: 0,
+        recipient_can_delegate: get_optional_named_arg("recipient_can_delegate")
+            .unwrap_or_default(),
+        rewards_to_admin: get_optional_named_arg("rewards_to_admin").unwrap_or_default(),
+        delegated_amount: Amount::zero(),
+        unbonding_amount: Amount::zero(),
+        rewards_paid: Amount::zero(),
+        clock_source,
+        drip_schedule,
+        rebase_scheduled_amount: Amount::zero(),
+        rebase_vested_amount: Amount::zero(),
+        milestone_count: milestones.len() as u32,
+        completed_milestones_amount: Amount::zero(),
+        condition_contract: get_optional_named_arg("condition_contract"),
+        release_destination: get_release_destination_arg(),
+        pending_release_destination: None,
+        reclaimed_amount: Amount::zero(),
+        claim_deadline: get_optional_time_arg("claim_deadline", TimeArg::Timestamp, clock_source),
+        strict_pause: get_optional_named_arg("strict_pause").unwrap_or_default(),
+        pause_mode,
+        forfeited_amount: Amount::zero(),
+        pending_acceptance: requires_acceptance,
+        acceptance_timestamp: None,
+        agreement_hash: None,
+        start_trigger,
+        cliff_offset,
+        start_timestamp: None,
+        lifecycle: Lifecycle::Pending,
+        funder_count: 0,
+        deposited_amount: Amount::zero(),
+        payout_split_count: 0,
+        fee_bps: get_optional_named_arg("fee_bps").unwrap_or_default(),
+        fee_collector: get_optional_named_arg("fee_collector"),
+        fees_paid: Amount::zero(),
+        transfer_count: 0,
+        last_transfer_id: None,
+    };
+    // A fee needs a collector, and can't take more than the whole payout.
+    if state.fee_collector.is_some() != (state.fee_bps != 0) || state.fee_bps > MAX_BASIS_POINTS {
+        runtime::revert(Error::InvalidFee);
+    }
+    let payout_splits = get_payout_splits_arg(&state);
+    state.payout_split_count = payout_splits.len() as u32;
+    if start_trigger == StartTrigger::Install {
+        state.start(current_time(&state));
+    }
+    state.activate_if_ready();
+    // The deadline can't be checked against a schedule that hasn't started yet.
+    if state.claim_deadline.is_some() && state.is_start_pending() {
+        runtime::revert(Error::InvalidClaimDeadline);
+    }
+    // The recipient has until the deadline to withdraw, so it can't come before full vesting.
+    if let (Some(claim_deadline), Some(full_vesting_time)) =
+        (state.claim_deadline, state.full_vesting_time(0))
+    {
+        if claim_deadline < full_vesting_time {
+            runtime::revert(Error::InvalidClaimDeadline);
+        }
+    }
+
+    let grant_info = GrantInfo {
+        terms_hash: get_optional_named_arg("terms_hash"),
+        grant_id: get_optional_named_arg("grant_id"),
+        metadata: get_optional_named_arg::<BTreeMap<String, String>>("metadata")
+            .unwrap_or_default(),
+    };
+    let grant_id = grant_info.grant_id.clone();
+    if let Some(grant_id) = &grant_id {
+        verify_grant_id(grant_id);
+    }
+    verify_metadata(&grant_info.metadata);
 
     let entry_points = get_entry_points();
     let named_keys = {
         let mut nk = NamedKeys::new();
-        nk.insert(ADMIN.to_string(), storage::new_uref(admin).into());
-        nk.insert(RECIPIENT.to_string(), storage::new_uref(recipient).into());
-        nk.insert(
-            CLIFF_AMOUNT.to_string(),
-            storage::new_uref(cliff_amount).into(),
-        );
-        nk.insert(
-            CLIFF_TIMESTAMP.to_string(),
-            storage::new_uref(cliff_timestamp).into(),
-        );
-        nk.insert(
-            DRIP_AMOUNT.to_string(),
-            storage::new_uref(drip_amount).into(),
-        );
-        nk.insert(
-            DRIP_DURATION.to_string(),
-            storage::new_uref(drip_duration).into(),
-        );
-        nk.insert(
-            TOTAL_AMOUNT.to_string(),
-            storage::new_uref(total_amount).into(),
-        );
+        nk.insert(STATE.to_string(), storage::new_uref(state).into());
+        nk.insert(GRANT_INFO.to_string(), storage::new_uref(grant_info).into());
         nk.insert(
-            ADMIN_RELEASE_DURATION.to_string(),
-            storage::new_uref(admin_release_duration).into(),
-        );
-        nk.insert(
-            LAST_PAUSE_TIMESTAMP.to_string(),
-            storage::new_uref(Time::zero()).into(),
-        );
-        nk.insert(
-            ON_PAUSE_DURATION.to_string(),
-            storage::new_uref(Time::zero()).into(),
-        );
-        nk.insert(PAUSE_FLAG.to_string(), storage::new_uref(false).into());
-        nk.insert(
-            RELEASED_AMOUNT.to_string(),
-            storage::new_uref(Amount::zero()).into(),
+            LAYOUT_VERSION.to_string(),
+            storage::new_uref(CURRENT_LAYOUT_VERSION).into(),
         );
+        put_new_list(&mut nk, MILESTONES, milestones);
+        put_new_list(&mut nk, PAYOUT_SPLITS, payout_splits);
         nk
     };
+    // The package access key stays with the installing account,
+    // which is then the only one able to upgrade the contract.
     let (contract_hash, _version) = storage::new_contract(
         entry_points,
         Some(named_keys),
-        Some(String::from("vesting_contract_package_hash")),
-        None,
+        Some(String::from(PACKAGE_HASH_KEY)),
+        Some(String::from(PACKAGE_ACCESS_KEY)),
     );
 
-    runtime::put_key("vesting_contract", contract_hash.into());
-    runtime::put_key(
-        "vesting_contract_hash",
-        storage::new_uref(contract_hash).into(),
-    );
+    store_contract_hash(contract_hash);
+    if let Some(grant_id) = grant_id {
+        register_grant(&grant_id, contract_hash);
+    }
+}
+
+fn verify_metadata(metadata: &BTreeMap<String, String>) {
+    let bytes: usize = metadata
+        .iter()
+        .map(|(key, value)| key.len() + value.len())
+        .sum();
+    if metadata.len() > MAX_METADATA_ENTRIES || bytes > MAX_METADATA_BYTES {
+        runtime::revert(Error::InvalidMetadata);
+    }
+}
+
+fn verify_grant_id(grant_id: &str) {
+    if grant_id.is_empty() || grant_id.len() > MAX_GRANT_ID_LENGTH {
+        runtime::revert(Error::InvalidGrantId);
+    }
+    if let Some(key) = runtime::get_key(GRANTS_KEY) {
+        let grants = key.into_uref().unwrap_or_revert_with(Error::UnexpectedType);
+        let existing: Option<ContractHash> =
+            storage::dictionary_get(grants, grant_id).unwrap_or_revert();
+        if existing.is_some() {
+            runtime::revert(Error::DuplicateGrantId);
+        }
+    }
+}
+
+// Lets the installing account, and off-chain tools, find the contract by its grant id.
+fn register_grant(grant_id: &str, contract_hash: ContractHash) {
+    let grants = match runtime::get_key(GRANTS_KEY) {
+        Some(key) => key.into_uref().unwrap_or_revert_with(Error::UnexpectedType),
+        None => storage::new_dictionary(GRANTS_KEY).unwrap_or_revert(),
+    };
+    storage::dictionary_put(grants, grant_id, contract_hash);
+}
+
+fn upgrade_contract() {
+    let package_hash = runtime::get_key(PACKAGE_HASH_KEY)
+        .and_then(Key::into_hash)
+        .map(ContractPackageHash::new)
+        .unwrap_or_revert_with(Error::MissingKey);
+    // Named keys of the previous version are carried over to the new one.
+    let (contract_hash, _version) =
+        storage::add_contract_version(package_hash, get_entry_points(), NamedKeys::new());
+
+    store_contract_hash(contract_hash);
+    runtime::call_contract::<()>(contract_hash, "migrate", runtime_args! {});
+}
+
+fn store_contract_hash(contract_hash: ContractHash) {
+    runtime::put_key(CONTRACT_KEY, contract_hash.into());
+    runtime::put_key(CONTRACT_HASH_KEY, storage::new_uref(contract_hash).into());
 }
 
 fn get_entry_points() -> EntryPoints {
@@ -196,6 +1081,48 @@ fn get_entry_points() -> EntryPoints {
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "deposit",
+        vec![
+            Parameter::new("purse", CLType::URef),
+            Parameter::new("amount", CLType::U512),
+            Parameter::new("transfer_id", u64::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_deposited_amount",
+        vec![Parameter::new("funder", AccountHash::cl_type())],
+        CLType::U512,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_fees_paid",
+        vec![],
+        CLType::U512,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "set_payout_splits",
+        vec![Parameter::new(
+            "payout_splits",
+            CLType::List(Box::new(<(AccountHash, u16)>::cl_type())),
+        )],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_grant_info",
+        vec![],
+        GrantInfo::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
     entry_points.add_entry_point(EntryPoint::new(
         "pause",
         vec![],
@@ -212,13 +1139,133 @@ fn get_entry_points() -> EntryPoints {
     ));
     entry_points.add_entry_point(EntryPoint::new(
         "withdraw",
+        vec![
+            Parameter::new("amount", U512::cl_type()),
+            Parameter::new("transfer_id", u64::cl_type()),
+        ],
+        U512::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "admin_release",
+        vec![
+            Parameter::new("amount", U512::cl_type()),
+            Parameter::new("transfer_id", u64::cl_type()),
+        ],
+        U512::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "accept_grant",
+        vec![Parameter::new("agreement_hash", <[u8; 32]>::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "cancel_grant",
+        vec![],
+        U512::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "reclaim_forfeited",
+        vec![],
+        U512::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "sweep_excess",
+        vec![],
+        U512::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "close",
+        vec![],
+        U512::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "sweep_expired",
+        vec![],
+        U512::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    for name in ["propose_release_destination", "confirm_release_destination"] {
+        entry_points.add_entry_point(EntryPoint::new(
+            name,
+            vec![
+                Parameter::new("release_destination", Key::cl_type()),
+                Parameter::new("release_destination_public_key", PublicKey::cl_type()),
+            ],
+            <()>::cl_type(),
+            EntryPointAccess::Public,
+            EntryPointType::Contract,
+        ));
+    }
+    entry_points.add_entry_point(EntryPoint::new(
+        "complete_milestone",
+        vec![Parameter::new("id", u32::cl_type())],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "accelerate",
+        vec![
+            Parameter::new("amount", U512::cl_type()),
+            Parameter::new("percentage", u8::cl_type()),
+        ],
+        U512::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "delegate",
+        vec![
+            Parameter::new(auction::ARG_DELEGATOR, PublicKey::cl_type()),
+            Parameter::new(auction::ARG_VALIDATOR, PublicKey::cl_type()),
+            Parameter::new(auction::ARG_AMOUNT, U512::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "undelegate",
+        vec![
+            Parameter::new(auction::ARG_DELEGATOR, PublicKey::cl_type()),
+            Parameter::new(auction::ARG_VALIDATOR, PublicKey::cl_type()),
+            Parameter::new(auction::ARG_AMOUNT, U512::cl_type()),
+        ],
+        <()>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "get_stake_return_purse",
+        vec![],
+        CLType::URef,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        "settle_unbonded",
         vec![Parameter::new("amount", U512::cl_type())],
         <()>::cl_type(),
         EntryPointAccess::Public,
         EntryPointType::Contract,
     ));
     entry_points.add_entry_point(EntryPoint::new(
-        "admin_release",
+        "migrate",
         vec![],
         <()>::cl_type(),
         EntryPointAccess::Public,
@@ -227,58 +1274,155 @@ fn get_entry_points() -> EntryPoints {
     entry_points
 }
 
-fn verify_admin_account() {
-    let admin: AccountHash = get_key(ADMIN);
+fn verify_lifecycle(state: &VestingState, allowed: &[Lifecycle]) {
+    if !allowed.contains(&state.lifecycle) {
+        runtime::revert(Error::InvalidState);
+    }
+}
+
+// Reverts once the grant is revoked or completed, and can't change anymore.
+fn verify_open(state: &VestingState) {
+    if state.lifecycle.is_final() {
+        runtime::revert(Error::InvalidState);
+    }
+}
+
+fn verify_admin_account(state: &VestingState) {
     let caller = runtime::get_caller();
-    if admin != caller {
+    if state.admin != caller {
         runtime::revert(Error::NotTheAdminAccount);
     }
 }
 
-fn verify_recipient_account() {
-    let recipient: AccountHash = get_key(RECIPIENT);
+fn verify_recipient_account(state: &VestingState) {
     let caller = runtime::get_caller();
-    if recipient != caller {
+    if state.recipient != caller {
         runtime::revert(Error::NotTheRecipientAccount);
     }
 }
 
-fn available_amount() -> U512 {
-    let current_timestamp = current_timestamp();
-    let cliff_timestamp: U512 = get_key(CLIFF_TIMESTAMP);
-    let last_pause_timestamp: U512 = get_key(LAST_PAUSE_TIMESTAMP);
-    let is_paused: bool = get_key(PAUSE_FLAG);
-    let on_pause_duration: U512 = get_key(ON_PAUSE_DURATION);
-    let total_paused_duration = on_pause_duration
-        + if is_paused {
-            current_timestamp - last_pause_timestamp
-        } else {
-            Time::zero()
-        };
-    let cliff_timestamp_adjusted = cliff_timestamp + total_paused_duration;
-    if current_timestamp < cliff_timestamp_adjusted {
-        Amount::zero()
+// The auction bonds from the delegator's main purse, so the stake and the motes it returns are
+// held by that account until they are returned to the contract. The recipient is only trusted
+// with them if the admin allowed it at install.
+fn verify_delegator_account(state: &VestingState) {
+    let caller = runtime::get_caller();
+    if state.admin == caller {
+        return;
+    }
+    if state.recipient != caller || !state.recipient_can_delegate {
+        runtime::revert(Error::NotAllowedToDelegate);
+    }
+    if state.pending_acceptance {
+        runtime::revert(Error::GrantNotAccepted);
+    }
+}
+
+fn verify_delegator_key(delegator: &PublicKey) {
+    if AccountHash::from(delegator) != runtime::get_caller() {
+        runtime::revert(Error::DelegatorKeyMismatch);
+    }
+}
+
+fn current_time(state: &VestingState) -> Time {
+    match state.clock_source {
+        ClockSource::BlockTime => runtime::get_blocktime().into(),
+        ClockSource::EraId => {
+            let era_id: EraId = runtime::call_contract(
+                system::get_auction(),
+                auction::METHOD_READ_ERA_ID,
+                runtime_args! {},
+            );
+            era_id.value()
+        }
+    }
+}
+
+// Appends `event` to the `events` dictionary.
+fn emit_event(event: VestingEvent) {
+    let events = get_or_create_dictionary(EVENTS);
+    let index: u64 = if runtime::has_key(EVENT_COUNT) {
+        get_key(EVENT_COUNT)
     } else {
-        let drip_duration: U512 = get_key(DRIP_DURATION);
-        let time_diff: Time = current_timestamp - cliff_timestamp_adjusted;
-        let available_drips = if drip_duration == Time::zero() {
-            Amount::zero()
-        } else {
-            time_diff / drip_duration
-        };
-        let total_amount: U512 = get_key(TOTAL_AMOUNT);
-        let drip_amount: U512 = get_key(DRIP_AMOUNT);
-        let released_amount: U512 = get_key(RELEASED_AMOUNT);
-        let mut counter: U512 = get_key(CLIFF_AMOUNT);
-        counter += drip_amount * available_drips;
-        counter = cmp::min(counter, total_amount);
-        counter - released_amount
+        0
+    };
+    storage::dictionary_put(events, &index.to_string(), event);
+    set_key(EVENT_COUNT, index + 1);
+}
+
+// Dictionaries are created on first use, so contracts upgraded from older versions get them too.
+fn get_or_create_dictionary(name: &str) -> URef {
+    match runtime::get_key(name) {
+        Some(key) => key.into_uref().unwrap_or_revert_with(Error::UnexpectedType),
+        None => storage::new_dictionary(name).unwrap_or_revert(),
+    }
+}
+
+// Account hashes are keyed by their hex, as the prefixed form is longer than a dictionary
+// item key can be.
+fn get_deposit(funder: &AccountHash) -> Amount {
+    match runtime::get_key(DEPOSITS) {
+        Some(key) => {
+            let deposits = key.into_uref().unwrap_or_revert_with(Error::UnexpectedType);
+            storage::dictionary_get(deposits, &funder.to_string())
+                .unwrap_or_revert()
+                .unwrap_or_default()
+        }
+        None => Amount::zero(),
+    }
+}
+
+fn read_list<T: CLTyped + FromBytes>(name: &str, count: u32) -> Vec<T> {
+    if count == 0 {
+        return Vec::new();
+    }
+    let list = get_or_create_dictionary(name);
+    (0..count)
+        .map(|index| {
+            storage::dictionary_get(list, &index.to_string())
+                .unwrap_or_revert()
+                .unwrap_or_revert_with(Error::MissingKey)
+        })
+        .collect()
+}
+
+fn read_list_item<T: CLTyped + FromBytes>(name: &str, index: u32) -> T {
+    storage::dictionary_get(get_or_create_dictionary(name), &index.to_string())
+        .unwrap_or_revert()
+        .unwrap_or_revert_with(Error::MissingKey)
+}
+
+// Items past the new length are left behind, and never read as the length is kept in the state.
+fn write_list<T: CLTyped + ToBytes + Clone>(name: &str, items: &[T]) {
+    let list = get_or_create_dictionary(name);
+    for (index, item) in items.iter().enumerate() {
+        storage::dictionary_put(list, &index.to_string(), item.clone());
     }
 }
 
-fn current_timestamp() -> U512 {
-    let time: u64 = runtime::get_blocktime().into();
-    time.into()
+fn write_list_item<T: CLTyped + ToBytes>(name: &str, index: u32, item: T) {
+    storage::dictionary_put(get_or_create_dictionary(name), &index.to_string(), item);
+}
+
+// Dictionaries are created in the current context, so the install session creates them in the
+// installing account's and hands them over to the contract.
+fn put_new_list<T: CLTyped + ToBytes>(named_keys: &mut NamedKeys, name: &str, items: Vec<T>) {
+    if items.is_empty() {
+        return;
+    }
+    let list = storage::new_dictionary(name).unwrap_or_revert();
+    runtime::remove_key(name);
+    for (index, item) in items.into_iter().enumerate() {
+        storage::dictionary_put(list, &index.to_string(), item);
+    }
+    named_keys.insert(name.to_string(), list.into());
+}
+
+fn read_state() -> VestingState {
+    get_key(STATE)
+}
+
+fn write_state(state: VestingState) {
+    set_key(STATE, state)
 }
 
 pub fn get_key<T: FromBytes + CLTyped>(name: &str) -> T {
@@ -304,10 +1448,423 @@ pub fn set_key<T: ToBytes + CLTyped>(name: &str, value: T) {
     }
 }
 
-pub fn transfer_out_clx_to_caller(purse_name: &str, amount: U512) {
+pub fn get_optional_named_arg<T: FromBytes>(name: &str) -> Option<T> {
+    let mut arg_size: usize = 0;
+    let ret = unsafe {
+        ext_ffi::casper_get_named_arg_size(
+            name.as_bytes().as_ptr(),
+            name.len(),
+            &mut arg_size as *mut usize,
+        )
+    };
+    match api_error::result_from(ret) {
+        Ok(_) => {}
+        Err(ApiError::MissingArgument) => return None,
+        Err(error) => runtime::revert(error),
+    }
+    let arg_bytes = if arg_size > 0 {
+        let data_non_null_ptr = contract_api::alloc_bytes(arg_size);
+        let ret = unsafe {
+            ext_ffi::casper_get_named_arg(
+                name.as_bytes().as_ptr(),
+                name.len(),
+                data_non_null_ptr.as_ptr(),
+                arg_size,
+            )
+        };
+        let data = unsafe { Vec::from_raw_parts(data_non_null_ptr.as_ptr(), arg_size, arg_size) };
+        api_error::result_from(ret).unwrap_or_revert();
+        data
+    } else {
+        Vec::new()
+    };
+    Some(bytesrepr::deserialize(arg_bytes).unwrap_or_revert_with(ApiError::InvalidArgument))
+}
+
+// Times are passed with their unit as a suffix of the argument name:
+// `_eras` for the era id clock, and either `_ms` or `_secs` for the block time clock.
+fn get_time_arg(name: &str, kind: TimeArg, clock_source: ClockSource) -> Time {
+    get_optional_time_arg(name, kind, clock_source)
+        .unwrap_or_revert_with(Error::InvalidTimeArgument)
+}
+
+// Same as `get_time_arg`, for an argument that can be left out.
+fn get_optional_time_arg(name: &str, kind: TimeArg, clock_source: ClockSource) -> Option<Time> {
+    if clock_source == ClockSource::EraId {
+        let eras: Time = get_optional_named_arg(&format!("{}_eras", name))?;
+        verify_era_range(eras, kind);
+        return Some(eras);
+    }
+    let ms: Option<Time> = get_optional_named_arg(&format!("{}_ms", name));
+    let secs: Option<Time> = get_optional_named_arg(&format!("{}_secs", name));
+    let time = match (ms, secs) {
+        (None, None) => return None,
+        (Some(ms), None) => ms,
+        (None, Some(secs)) => secs_to_ms(secs),
+        _ => runtime::revert(Error::InvalidTimeArgument),
+    };
+    verify_time_range(time, kind);
+    Some(time)
+}
+
+// Same as `get_time_arg`, for an optional list of timestamps.
+fn get_optional_timestamps_arg(name: &str, clock_source: ClockSource) -> Option<Vec<Time>> {
+    if clock_source == ClockSource::EraId {
+        let eras: Vec<Time> = get_optional_named_arg(&format!("{}_eras", name))?;
+        for era in &eras {
+            verify_era_range(*era, TimeArg::Timestamp);
+        }
+        return Some(eras);
+    }
+    let ms: Option<Vec<Time>> = get_optional_named_arg(&format!("{}_ms", name));
+    let secs: Option<Vec<Time>> = get_optional_named_arg(&format!("{}_secs", name));
+    let times: Vec<Time> = match (ms, secs) {
+        (None, None) => return None,
+        (Some(ms), None) => ms,
+        (None, Some(secs)) => secs.into_iter().map(secs_to_ms).collect(),
+        _ => runtime::revert(Error::InvalidTimeArgument),
+    };
+    for time in &times {
+        verify_time_range(*time, TimeArg::Timestamp);
+    }
+    Some(times)
+}
+
+fn secs_to_ms(secs: Time) -> Time {
+    secs.checked_mul(1000)
+        .unwrap_or_revert_with(Error::TimeOutOfRange)
+}
+
+fn verify_time_range(time: Time, kind: TimeArg) {
+    let in_range = match kind {
+        TimeArg::Timestamp => time == 0 || (MIN_TIMESTAMP_MS..=MAX_TIMESTAMP_MS).contains(&time),
+        TimeArg::Duration => time <= MAX_DURATION_MS,
+    };
+    if !in_range {
+        runtime::revert(Error::TimeOutOfRange);
+    }
+}
+
+// Catches times passed in another unit, such as milliseconds, for the era id clock.
+fn verify_era_range(eras: Time, kind: TimeArg) {
+    let max = match kind {
+        TimeArg::Timestamp => MAX_ERA_ID,
+        TimeArg::Duration => MAX_DURATION_ERAS,
+    };
+    if eras > max {
+        runtime::revert(Error::TimeOutOfRange);
+    }
+}
+
+// A release destination is passed either as `release_destination`, the key of an account
+// or of a purse with add access rights, or as `release_destination_public_key`.
+fn get_release_destination_arg() -> Option<ReleaseDestination> {
+    let key: Option<Key> = get_optional_named_arg("release_destination");
+    let public_key: Option<PublicKey> = get_optional_named_arg("release_destination_public_key");
+    match (key, public_key) {
+        (None, None) => None,
+        (Some(Key::Account(account_hash)), None) => Some(ReleaseDestination::Account(account_hash)),
+        (Some(Key::URef(purse)), None) if purse.is_addable() => {
+            Some(ReleaseDestination::Purse(purse))
+        }
+        (None, Some(public_key)) => Some(ReleaseDestination::Account(public_key.to_account_hash())),
+        _ => runtime::revert(Error::InvalidReleaseDestination),
+    }
+}
+
+// Milestones are passed as a list of names and amounts, with an optional list of deadlines
+// of the same length, where 0 stands for no deadline.
+fn get_milestones_arg(clock_source: ClockSource) -> Vec<Milestone> {
+    let milestones: Vec<(String, Amount)> =
+        get_optional_named_arg("milestones").unwrap_or_default();
+    let deadlines = get_optional_timestamps_arg("milestone_deadlines", clock_source)
+        .unwrap_or_else(|| vec![0; milestones.len()]);
+    if deadlines.len() != milestones.len() {
+        runtime::revert(Error::InvalidMilestones);
+    }
+    milestones
+        .into_iter()
+        .zip(deadlines)
+        .map(|((name, amount), deadline)| Milestone {
+            name,
+            amount,
+            deadline: if deadline == 0 { None } else { Some(deadline) },
+            status: MilestoneStatus::Pending,
+        })
+        .collect()
+}
+
+// Payout splits are passed as a list of target accounts and basis points. Their targets
+// are distinct from each other and from the recipient, and their shares add up to at most
+// the whole withdrawal.
+fn get_payout_splits_arg(state: &VestingState) -> Vec<PayoutSplit> {
+    let payout_splits: Vec<(AccountHash, u16)> =
+        get_optional_named_arg("payout_splits").unwrap_or_default();
+    if payout_splits.len() > MAX_PAYOUT_SPLITS {
+        runtime::revert(Error::InvalidPayoutSplits);
+    }
+    let mut total_basis_points: u32 = 0;
+    for (index, (target, basis_points)) in payout_splits.iter().enumerate() {
+        if *basis_points == 0
+            || *target == state.recipient
+            || payout_splits[..index]
+                .iter()
+                .any(|(other, _)| other == target)
+        {
+            runtime::revert(Error::InvalidPayoutSplits);
+        }
+        total_basis_points += u32::from(*basis_points);
+    }
+    if total_basis_points > u32::from(MAX_BASIS_POINTS) {
+        runtime::revert(Error::InvalidPayoutSplits);
+    }
+    payout_splits
+        .into_iter()
+        .map(|(target, basis_points)| PayoutSplit {
+            target,
+            basis_points,
+        })
+        .collect()
+}
+
+fn get_or_create_vesting_purse() -> URef {
+    match runtime::get_key(PURSE_NAME) {
+        Some(purse_key) => purse_key.into_uref().unwrap_or_revert(),
+        None => {
+            let new_purse = system::create_purse();
+            runtime::put_key(PURSE_NAME, new_purse.into());
+            new_purse
+        }
+    }
+}
+
+pub fn get_purse(purse_name: &str) -> URef {
     let key = runtime::get_key(purse_name).unwrap_or_revert_with(Error::LocalPurseKeyMissing);
-    let local_purse: URef = key.into_uref().unwrap_or_revert_with(Error::UnexpectedType);
+    key.into_uref().unwrap_or_revert_with(Error::UnexpectedType)
+}
+
+// Sends funds taken back by the admin to the release destination,
+// or to the calling admin account if none is set.
+fn transfer_out_to_release_destination(
+    destination: Option<ReleaseDestination>,
+    amount: U512,
+    transfer_id: u64,
+) {
+    let local_purse = get_purse(PURSE_NAME);
+    let id = Some(transfer_id);
+    let result = match destination.unwrap_or(ReleaseDestination::Account(runtime::get_caller())) {
+        ReleaseDestination::Account(account_hash) => {
+            system::transfer_from_purse_to_account(local_purse, account_hash, amount, id).map(drop)
+        }
+        ReleaseDestination::Purse(purse) => {
+            system::transfer_from_purse_to_purse(local_purse, purse, amount, id)
+        }
+    };
+    result.unwrap_or_revert_with(Error::PurseTransferErr);
+}
+
+// Ids of the transfers made by one call. The main transfer, to the recipient, from the depositor
+// or to the release destination, takes `main`. Every other one takes its own id from `next`.
+struct TransferIds {
+    package_hash: ContractPackageHash,
+    sequence: u64,
+    main: u64,
+    next_index: u64,
+}
+
+impl TransferIds {
+    fn next(&mut self) -> u64 {
+        let transfer_id = derive_transfer_id(self.package_hash, self.sequence, self.next_index);
+        self.next_index += 1;
+        transfer_id
+    }
+}
+
+// Takes the ids of the transfers of the next call that moves motes, and records its main id in
+// the state if the main transfer moves a non-zero `main_amount`. The main id is `transfer_id`
+// if passed, and is derived like the others otherwise.
+fn next_transfer_ids(
+    state: &mut VestingState,
+    transfer_id: Option<u64>,
+    main_amount: U512,
+) -> TransferIds {
+    let package_hash = current_package_hash();
+    let sequence = state.transfer_count;
+    let main = transfer_id.unwrap_or_else(|| derive_transfer_id(package_hash, sequence, 0));
+    state.transfer_count += 1;
+    if !main_amount.is_zero() {
+        state.last_transfer_id = Some(main);
+    }
+    TransferIds {
+        package_hash,
+        sequence,
+        main,
+        next_index: 1,
+    }
+}
+
+// The first 8 bytes, little-endian, of the hash of the contract package hash followed by the
+// sequence number of the call and the index of the transfer in the call, both little-endian.
+fn derive_transfer_id(package_hash: ContractPackageHash, sequence: u64, index: u64) -> u64 {
+    let mut bytes = package_hash.value().to_vec();
+    bytes.extend_from_slice(&sequence.to_le_bytes());
+    bytes.extend_from_slice(&index.to_le_bytes());
+    let hash = runtime::blake2b(bytes);
+    let mut transfer_id = [0u8; 8];
+    transfer_id.copy_from_slice(&hash[..8]);
+    u64::from_le_bytes(transfer_id)
+}
+
+fn current_package_hash() -> ContractPackageHash {
+    match runtime::get_call_stack().last() {
+        Some(CallStackElement::StoredContract {
+            contract_package_hash,
+            ..
+        })
+        | Some(CallStackElement::StoredSession {
+            contract_package_hash,
+            ..
+        }) => *contract_package_hash,
+        _ => runtime::revert(Error::UnexpectedVestingErr),
+    }
+}
+
+// A fee taken on a payout, to be sent to the fee collector once the state is written.
+struct Fee {
+    collector: Option<AccountHash>,
+    amount: U512,
+    fees_paid: U512,
+}
+
+// Adds the fee on a payout of `amount` to the fees paid. The fee is rounded down,
+// so that the payee never loses more than `fee_bps` of the payout.
+fn charge_fee(state: &mut VestingState, amount: U512) -> Fee {
+    let fee = state.fee(amount);
+    state.fees_paid += fee;
+    Fee {
+        collector: state.fee_collector,
+        amount: fee,
+        fees_paid: state.fees_paid,
+    }
+}
+
+fn pay_fee(fee: Fee, now: Time, transfer_ids: &mut TransferIds) {
+    let collector = match fee.collector {
+        Some(collector) if !fee.amount.is_zero() => collector,
+        _ => return,
+    };
+    let local_purse = get_purse(PURSE_NAME);
+    let transfer_id = transfer_ids.next();
+    system::transfer_from_purse_to_account(local_purse, collector, fee.amount, Some(transfer_id))
+        .unwrap_or_revert_with(Error::PurseTransferErr);
+    emit_event(VestingEvent::FeeCharged {
+        amount: fee.amount,
+        fees_paid: fee.fees_paid,
+        transfer_id,
+        timestamp: now,
+    });
+}
+
+// Splits `amount` taken back from the grant between its funders, in proportion to what each
+// of them deposited. The admin's own share is left out, to go to the release destination.
+fn funder_shares(state: &VestingState, amount: U512) -> Vec<(AccountHash, U512)> {
+    // Deposits above the grant are counted too, so that shares never add up to more than `amount`.
+    let funded_amount = cmp::max(state.deposited_amount, state.total_amount);
+    if funded_amount.is_zero() {
+        return Vec::new();
+    }
+    read_list::<AccountHash>(FUNDERS, state.funder_count)
+        .into_iter()
+        .filter(|funder| *funder != state.admin)
+        .map(|funder| (funder, amount * get_deposit(&funder) / funded_amount))
+        .filter(|(_, share)| !share.is_zero())
+        .collect()
+}
+
+// Takes the shares of `amount` off the deposits of every funder, the Admin included, so that later
+// refunds only cover what is still held for them.
+fn debit_deposits(state: &mut VestingState, amount: U512) {
+    let funded_amount = cmp::max(state.deposited_amount, state.total_amount);
+    if funded_amount.is_zero() {
+        return;
+    }
+    let deposits = get_or_create_dictionary(DEPOSITS);
+    let mut debited = U512::zero();
+    for funder in read_list::<AccountHash>(FUNDERS, state.funder_count) {
+        let deposited = get_deposit(&funder);
+        let share = amount * deposited / funded_amount;
+        storage::dictionary_put(deposits, &funder.to_string(), deposited - share);
+        debited += share;
+    }
+    state.deposited_amount -= debited;
+}
+
+// Sends the funder shares of `amount` back to them, and the rest, which includes the rounding
+// remainder and whatever was not deposited through `deposit`, to the release destination.
+fn refund(
+    shares: Vec<(AccountHash, U512)>,
+    destination: Option<ReleaseDestination>,
+    amount: U512,
+    now: Time,
+    transfer_ids: &mut TransferIds,
+) {
+    let local_purse = get_purse(PURSE_NAME);
+    let mut remaining = amount;
+    for (funder, share) in shares {
+        let transfer_id = transfer_ids.next();
+        system::transfer_from_purse_to_account(local_purse, funder, share, Some(transfer_id))
+            .unwrap_or_revert_with(Error::PurseTransferErr);
+        remaining -= share;
+        emit_event(VestingEvent::Refunded {
+            funder,
+            amount: share,
+            transfer_id,
+            timestamp: now,
+        });
+    }
+    if !remaining.is_zero() {
+        transfer_out_to_release_destination(destination, remaining, transfer_ids.main);
+    }
+}
+
+// What `refund` sends to the release destination once the funders have their shares.
+fn refund_remainder(shares: &[(AccountHash, U512)], amount: U512) -> U512 {
+    shares
+        .iter()
+        .fold(amount, |remaining, (_, share)| remaining - *share)
+}
+
+// Sends each split target its share of a withdrawn `amount`, and the rest to the recipient,
+// so that the transfers add up to `amount`.
+fn pay_out(payout_splits: &[PayoutSplit], amount: U512, transfer_ids: &mut TransferIds) {
+    let local_purse = get_purse(PURSE_NAME);
+    let mut remaining = amount;
+    for split in payout_splits {
+        let share = split.share(amount);
+        if share.is_zero() {
+            continue;
+        }
+        let id = Some(transfer_ids.next());
+        system::transfer_from_purse_to_account(local_purse, split.target, share, id)
+            .unwrap_or_revert_with(Error::PurseTransferErr);
+        remaining -= share;
+    }
+    if !remaining.is_zero() {
+        transfer_out_clx_to_caller(PURSE_NAME, remaining, transfer_ids.main);
+    }
+}
+
+// What `pay_out` sends to the recipient once the split targets have their shares.
+fn payout_remainder(payout_splits: &[PayoutSplit], amount: U512) -> U512 {
+    payout_splits
+        .iter()
+        .fold(amount, |remaining, split| remaining - split.share(amount))
+}
+
+pub fn transfer_out_clx_to_caller(purse_name: &str, amount: U512, transfer_id: u64) {
+    let local_purse = get_purse(purse_name);
+    let id = Some(transfer_id);
 
-    system::transfer_from_purse_to_account(local_purse, runtime::get_caller(), amount, None)
+    system::transfer_from_purse_to_account(local_purse, runtime::get_caller(), amount, id)
         .unwrap_or_revert_with(Error::PurseTransferErr);
 }
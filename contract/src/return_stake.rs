@@ -0,0 +1,33 @@
+#![no_main]
+#![no_std]
+
+use contract::{
+    contract_api::{account, runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{runtime_args, ContractHash, RuntimeArgs, URef, U512};
+
+// Session code that executes in the delegator's context.
+// Once the unbonding delay has passed, undelegated motes land in the delegator's `main_purse`.
+// This session moves `amount` of them back into the vesting purse and lets the contract
+// settle them against the unbonding principal. Anything above the principal is paid out as rewards.
+#[no_mangle]
+pub extern "C" fn call() {
+    let vesting_contract_hash: ContractHash = runtime::get_named_arg("vesting_contract_hash");
+    let amount: U512 = runtime::get_named_arg("amount");
+    // The returned purse only has ADD access rights.
+    let vesting_purse: URef = runtime::call_contract(
+        vesting_contract_hash,
+        "get_stake_return_purse",
+        runtime_args! {},
+    );
+    system::transfer_from_purse_to_purse(account::get_main_purse(), vesting_purse, amount, None)
+        .unwrap_or_revert();
+    runtime::call_contract::<()>(
+        vesting_contract_hash,
+        "settle_unbonded",
+        runtime_args! {
+            "amount" => amount
+        },
+    );
+}
@@ -0,0 +1,95 @@
+#![no_main]
+#![no_std]
+
+extern crate alloc;
+
+mod args;
+
+use alloc::string::String;
+use args::has_named_arg;
+use contract::{
+    contract_api::{account, runtime, storage, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    bytesrepr::{FromBytes, ToBytes},
+    runtime_args, ApiError, CLTyped, ContractHash, RuntimeArgs, U512,
+};
+
+const WITHDRAWN_AMOUNT: &str = "vesting_withdrawn_amount";
+const ADMIN_RELEASED_AMOUNT: &str = "vesting_admin_released_amount";
+const FEES_PAID: &str = "vesting_fees_paid";
+const ACCELERATED_AMOUNT: &str = "vesting_accelerated_amount";
+const CANCELLED_AMOUNT: &str = "vesting_cancelled_amount";
+const CLOSED_AMOUNT: &str = "vesting_closed_amount";
+const SWEPT_EXPIRED_AMOUNT: &str = "vesting_swept_expired_amount";
+const SWEPT_EXCESS_AMOUNT: &str = "vesting_swept_excess_amount";
+const RECLAIMED_FORFEITED_AMOUNT: &str = "vesting_reclaimed_forfeited_amount";
+
+// Session code that executes in the callers context.
+// It calls the `method` entry point of the vesting contract by its hash,
+// and stores the value it returns under the caller's named keys for easy inspection.
+// The arguments of the entry point are passed on, the optional ones only when given.
+#[no_mangle]
+pub extern "C" fn call() {
+    let vesting_contract_hash: ContractHash = runtime::get_named_arg("vesting_contract_hash");
+    let method: String = runtime::get_named_arg("method");
+    let mut args = runtime_args! {};
+    let result_key = match method.as_str() {
+        "pause" | "unpause" | "accept_grant" | "complete_milestone" => {
+            forward_arg::<[u8; 32]>(&mut args, "agreement_hash");
+            forward_arg::<u32>(&mut args, "id");
+            // These entry points return nothing.
+            runtime::call_contract::<()>(vesting_contract_hash, &method, args);
+            return;
+        }
+        "withdraw" => {
+            forward_arg::<U512>(&mut args, "amount");
+            forward_arg::<u64>(&mut args, "transfer_id");
+            // The contract returns the whole amount, while fees and payout splits go elsewhere,
+            // so what reached the caller is read off their main purse.
+            let main_purse = account::get_main_purse();
+            let balance_before = system::get_purse_balance(main_purse).unwrap_or_revert();
+            runtime::call_contract::<U512>(vesting_contract_hash, &method, args);
+            let balance_after = system::get_purse_balance(main_purse).unwrap_or_revert();
+            store_result(WITHDRAWN_AMOUNT, balance_after - balance_before);
+            return;
+        }
+        "admin_release" => {
+            forward_arg::<U512>(&mut args, "amount");
+            forward_arg::<u64>(&mut args, "transfer_id");
+            ADMIN_RELEASED_AMOUNT
+        }
+        "accelerate" => {
+            forward_arg::<U512>(&mut args, "amount");
+            forward_arg::<u8>(&mut args, "percentage");
+            ACCELERATED_AMOUNT
+        }
+        "get_fees_paid" => FEES_PAID,
+        "cancel_grant" => CANCELLED_AMOUNT,
+        "close" => CLOSED_AMOUNT,
+        "sweep_expired" => SWEPT_EXPIRED_AMOUNT,
+        "sweep_excess" => SWEPT_EXCESS_AMOUNT,
+        "reclaim_forfeited" => RECLAIMED_FORFEITED_AMOUNT,
+        _ => runtime::revert(ApiError::InvalidArgument),
+    };
+    let result: U512 = runtime::call_contract(vesting_contract_hash, &method, args);
+    store_result(result_key, result);
+}
+
+fn forward_arg<T: CLTyped + FromBytes + ToBytes>(args: &mut RuntimeArgs, name: &str) {
+    if has_named_arg(name) {
+        let value: T = runtime::get_named_arg(name);
+        args.insert(name, value).unwrap_or_revert();
+    }
+}
+
+fn store_result(name: &str, value: U512) {
+    match runtime::get_key(name) {
+        Some(key) => {
+            let uref = key.into_uref().unwrap_or_revert();
+            storage::write(uref, value);
+        }
+        None => runtime::put_key(name, storage::new_uref(value).into()),
+    }
+}
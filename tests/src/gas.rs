@@ -0,0 +1,94 @@
+use std::{collections::BTreeMap, env, fs, path::PathBuf};
+
+use casper_types::U512;
+
+// Costs are compared against this file, one `<entry point> <cost>` pair per line.
+// Run the tests with `UPDATE_GAS_BASELINE=1` to rewrite it with the measured costs.
+const BASELINE_FILE: &str = "gas_baseline.txt";
+const UPDATE_BASELINE_VAR: &str = "UPDATE_GAS_BASELINE";
+
+fn baseline_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(BASELINE_FILE)
+}
+
+fn read_baseline() -> BTreeMap<String, U512> {
+    let content = fs::read_to_string(baseline_path()).unwrap_or_default();
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let mut parts = line.split_whitespace();
+            let name = parts.next().expect("should have entry point name.");
+            let cost = parts.next().expect("should have cost.");
+            (
+                name.to_string(),
+                U512::from_dec_str(cost).expect("should be a decimal cost."),
+            )
+        })
+        .collect()
+}
+
+fn write_baseline(costs: &[(&str, U512)]) {
+    let mut content =
+        String::from("# Gas cost of each vesting entry point, see tests/src/gas.rs\n");
+    for (name, cost) in costs {
+        content.push_str(&format!("{} {}\n", name, cost));
+    }
+    fs::write(baseline_path(), content).expect("should write gas baseline.");
+}
+
+// Change of `cost` from `baseline`, in percent.
+fn format_change(cost: U512, baseline: U512) -> String {
+    if baseline.is_zero() {
+        return "-".to_string();
+    }
+    let ratio = cost.as_u128() as f64 / baseline.as_u128() as f64;
+    format!("{:+.1}%", (ratio - 1.0) * 100.0)
+}
+
+/// Prints a table of the measured costs, with their change from the baseline, and panics if
+/// any of them is over the baseline, or has no baseline entry.
+pub fn check_against_baseline(costs: &[(&str, U512)]) {
+    if env::var(UPDATE_BASELINE_VAR).is_ok() {
+        write_baseline(costs);
+        return;
+    }
+    let baseline = read_baseline();
+    let mut exceeded = Vec::new();
+    let mut missing = Vec::new();
+    println!(
+        "{:<28} {:>16} {:>16} {:>8}",
+        "entry point", "cost", "baseline", "change"
+    );
+    for (name, cost) in costs {
+        match baseline.get(*name) {
+            Some(limit) => {
+                println!(
+                    "{:<28} {:>16} {:>16} {:>8}",
+                    name,
+                    cost,
+                    limit,
+                    format_change(*cost, *limit)
+                );
+                if cost > limit {
+                    exceeded.push(*name);
+                }
+            }
+            None => {
+                println!("{:<28} {:>16} {:>16} {:>8}", name, cost, "-", "-");
+                missing.push(*name);
+            }
+        }
+    }
+    assert!(
+        missing.is_empty(),
+        "No gas baseline for: {}, run `make update-gas-baseline`",
+        missing.join(", ")
+    );
+    assert!(
+        exceeded.is_empty(),
+        "Gas cost over baseline for: {}",
+        exceeded.join(", ")
+    );
+}
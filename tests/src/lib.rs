@@ -3,3 +3,6 @@ mod vesting;
 
 #[cfg(test)]
 mod tests;
+
+#[cfg(test)]
+mod gas;
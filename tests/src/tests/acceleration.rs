@@ -0,0 +1,54 @@
+use vesting_types::VestingEvent;
+
+use crate::vesting::{Vesting, VestingConfig};
+
+#[test]
+fn test_accelerate_percentage() {
+    let config: VestingConfig = Default::default();
+    let mut vesting = Vesting::deploy();
+    vesting.accelerate_percentage(vesting.admin_account.1, 50);
+    vesting.withdraw(vesting.ali_account.1, 500);
+    assert_eq!(
+        vesting.get_event(0),
+        VestingEvent::Accelerated {
+            amount: 500.into(),
+            timestamp: 0
+        }
+    );
+    assert_eq!(vesting.get_event_count(), 1);
+    // The cliff and the drips vest the remaining half of what they used to.
+    vesting.set_block_time(config.cliff_timestamp);
+    vesting.withdraw(vesting.ali_account.1, 1);
+    // The schedule still ends when it used to.
+    vesting.set_block_time(config.cliff_timestamp + config.drip_duration * 200);
+    vesting.withdraw(vesting.ali_account.1, 499);
+    assert_eq!(vesting.get_released_amount(), config.total_amount.as_u64());
+}
+
+#[test]
+fn test_accelerate_amount_after_cliff() {
+    let config: VestingConfig = Default::default();
+    let mut vesting = Vesting::deploy();
+    vesting.set_block_time(config.cliff_timestamp);
+    vesting.accelerate(vesting.admin_account.1, 98);
+    vesting.withdraw(vesting.ali_account.1, 100);
+    let state = vesting.get_state();
+    assert_eq!(state.rebase_scheduled_amount, config.cliff_amount);
+    assert_eq!(state.rebase_vested_amount, 100.into());
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(3)")]
+fn test_accelerate_by_not_admin() {
+    let mut vesting = Vesting::deploy();
+    vesting.accelerate(vesting.ali_account.1, 100);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(24)")]
+fn test_accelerate_more_than_unvested() {
+    let config: VestingConfig = Default::default();
+    let mut vesting = Vesting::deploy();
+    vesting.set_block_time(config.cliff_timestamp);
+    vesting.accelerate(vesting.admin_account.1, config.total_amount.as_u64());
+}
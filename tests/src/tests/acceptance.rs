@@ -0,0 +1,119 @@
+use vesting_types::{Lifecycle, VestingEvent};
+
+use crate::vesting::{Vesting, VestingConfig};
+
+pub(super) fn acceptance_config() -> VestingConfig {
+    VestingConfig {
+        requires_acceptance: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(38)")]
+fn test_withdraw_before_acceptance() {
+    let config = acceptance_config();
+    let cliff_timestamp = config.cliff_timestamp;
+    let cliff_amount = config.cliff_amount.as_u64();
+    let mut vesting = Vesting::deploy_with_config(config);
+    vesting.set_block_time(cliff_timestamp);
+    vesting.withdraw(vesting.ali_account.1, cliff_amount);
+}
+
+#[test]
+fn test_accept_grant() {
+    let config = acceptance_config();
+    let cliff_timestamp = config.cliff_timestamp;
+    let cliff_amount = config.cliff_amount.as_u64();
+    let mut vesting = Vesting::deploy_with_config(config);
+    assert!(vesting.get_state().pending_acceptance);
+    vesting.set_block_time(cliff_timestamp);
+    vesting.accept_grant(vesting.ali_account.1, Some([7u8; 32]));
+    let state = vesting.get_state();
+    assert!(!state.pending_acceptance);
+    assert_eq!(state.acceptance_timestamp, Some(cliff_timestamp));
+    assert_eq!(state.agreement_hash, Some([7u8; 32]));
+    assert_eq!(
+        vesting.get_event(0),
+        VestingEvent::GrantAccepted {
+            agreement_hash: Some([7u8; 32]),
+            timestamp: cliff_timestamp
+        }
+    );
+    vesting.withdraw(vesting.ali_account.1, cliff_amount);
+    assert_eq!(vesting.get_released_amount(), cliff_amount);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(43)")]
+fn test_accept_grant_twice() {
+    let mut vesting = Vesting::deploy_with_config(acceptance_config());
+    vesting.accept_grant(vesting.ali_account.1, None);
+    vesting.accept_grant(vesting.ali_account.1, None);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(4)")]
+fn test_accept_grant_by_admin() {
+    let mut vesting = Vesting::deploy_with_config(acceptance_config());
+    vesting.accept_grant(vesting.admin_account.1, None);
+}
+
+#[test]
+fn test_cancel_grant() {
+    let config = acceptance_config();
+    let total_amount = config.total_amount;
+    let mut vesting = Vesting::deploy_with_config(config);
+    vesting.cancel_grant(vesting.admin_account.1);
+    let state = vesting.get_state();
+    assert!(!state.pending_acceptance);
+    assert_eq!(state.reclaimed_amount, total_amount);
+    assert_eq!(vesting.get_purse_balance(), 0);
+    assert_eq!(
+        vesting.get_event(0),
+        VestingEvent::GrantCancelled {
+            amount: total_amount,
+            timestamp: 0
+        }
+    );
+}
+
+#[test]
+fn test_cancel_unfunded_grant() {
+    let config = acceptance_config();
+    let total_amount = config.total_amount;
+    let mut vesting = Vesting::deploy_with_config(VestingConfig {
+        fund_on_deploy: false,
+        ..config
+    });
+    vesting.cancel_grant(vesting.admin_account.1);
+    let state = vesting.get_state();
+    assert_eq!(state.lifecycle, Lifecycle::Revoked);
+    assert_eq!(state.reclaimed_amount, total_amount);
+    assert_eq!(state.transfer_count, 0);
+}
+
+#[test]
+fn test_cancel_partly_funded_grant() {
+    let mut vesting = Vesting::deploy_with_config(VestingConfig {
+        fund_on_deploy: false,
+        ..acceptance_config()
+    });
+    vesting.deposit(vesting.bob_account.1, 300.into());
+    let bob_balance = vesting.get_account_balance(vesting.bob_account.1);
+    vesting.cancel_grant(vesting.admin_account.1);
+    assert_eq!(vesting.get_purse_balance(), 0);
+    // Bob gets back the whole deposit.
+    assert_eq!(
+        vesting.get_account_balance(vesting.bob_account.1),
+        bob_balance + 300
+    );
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(43)")]
+fn test_cancel_grant_after_acceptance() {
+    let mut vesting = Vesting::deploy_with_config(acceptance_config());
+    vesting.accept_grant(vesting.ali_account.1, None);
+    vesting.cancel_grant(vesting.admin_account.1);
+}
@@ -0,0 +1,132 @@
+use crate::vesting::{Vesting, VestingConfig};
+
+#[test]
+fn test_vesting_deploy() {
+    let vesting = Vesting::deploy();
+    let config: VestingConfig = Default::default();
+    let amount = vesting.get_total_amount();
+    assert_eq!(amount, config.total_amount.as_u64());
+}
+
+#[test]
+fn test_vesting_state_after_deploy() {
+    let vesting = Vesting::deploy();
+    let config: VestingConfig = Default::default();
+    let state = vesting.get_state();
+    assert_eq!(state.admin, vesting.admin_account.1);
+    assert_eq!(state.recipient, vesting.ali_account.1);
+    assert_eq!(state.cliff_timestamp, config.cliff_timestamp);
+    assert_eq!(state.cliff_amount, config.cliff_amount);
+    assert_eq!(state.drip_duration, config.drip_duration);
+    assert_eq!(state.drip_amount, config.drip_amount);
+    assert_eq!(state.admin_release_duration, config.admin_release_duration);
+    assert!(state.released_amount.is_zero());
+    assert!(!state.is_paused);
+}
+
+#[test]
+fn test_deploy_with_time_args_in_secs() {
+    let config = VestingConfig {
+        drip_duration: 3_000,
+        admin_release_duration: 123_000,
+        time_args_in_secs: true,
+        ..Default::default()
+    };
+    let cliff_timestamp = config.cliff_timestamp;
+    let vesting = Vesting::deploy_with_config(config);
+    let state = vesting.get_state();
+    assert_eq!(state.cliff_timestamp, cliff_timestamp);
+    assert_eq!(state.drip_duration, 3_000);
+    assert_eq!(state.admin_release_duration, 123_000);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(21)")]
+fn test_deploy_with_secs_passed_as_ms() {
+    // 2020/03/01 @ 1:02am (UTC) in Unix seconds.
+    Vesting::deploy_with_config(VestingConfig {
+        cliff_timestamp: 1_583_024_523,
+        ..Default::default()
+    });
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(21)")]
+fn test_deploy_with_duration_out_of_range() {
+    Vesting::deploy_with_config(VestingConfig {
+        drip_duration: u64::MAX,
+        ..Default::default()
+    });
+}
+
+#[test]
+fn test_withdraw() {
+    let mut vesting = Vesting::deploy();
+    let config: VestingConfig = Default::default();
+    vesting.set_block_time(config.cliff_timestamp);
+    vesting.withdraw(vesting.ali_account.1, 1);
+    let amount = vesting.get_released_amount();
+    assert_eq!(amount, 1);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(4)")]
+fn test_withdraw_incorrect_recepient() {
+    let mut vesting = Vesting::deploy();
+    let config: VestingConfig = Default::default();
+    vesting.set_block_time(config.cliff_timestamp);
+    vesting.withdraw(vesting.bob_account.1, 1);
+}
+
+#[test]
+fn test_pause_by_admin() {
+    let mut vesting = Vesting::deploy();
+    vesting.pause(vesting.admin_account.1);
+    let status = vesting.get_pause_status();
+    assert!(status, "The contract is not paused");
+}
+
+#[test]
+fn test_unpause_by_admin() {
+    let mut vesting = Vesting::deploy();
+    vesting.pause(vesting.admin_account.1);
+    vesting.unpause(vesting.admin_account.1);
+    let status = vesting.get_pause_status();
+    assert!(!status, "The contract is still paused");
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(3)")]
+fn test_pause_by_not_admin() {
+    let mut vesting = Vesting::deploy();
+    vesting.pause(vesting.ali_account.1);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(3)")]
+fn test_unpause_by_not_admin() {
+    let mut vesting = Vesting::deploy();
+    vesting.pause(vesting.admin_account.1);
+    vesting.unpause(vesting.ali_account.1);
+}
+
+#[test]
+fn test_admin_release() {
+    let config: VestingConfig = Default::default();
+    let mut vesting = Vesting::deploy();
+    vesting.pause(vesting.admin_account.1);
+    vesting.set_block_time(config.admin_release_duration);
+    vesting.admin_release(vesting.admin_account.1);
+    let amount = vesting.get_released_amount();
+    assert_eq!(amount, config.total_amount.as_u64());
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(3)")]
+fn test_admin_release_by_not_admin() {
+    let mut vesting = Vesting::deploy();
+    let config: VestingConfig = Default::default();
+    vesting.pause(vesting.admin_account.1);
+    vesting.set_block_time(config.admin_release_duration);
+    vesting.admin_release(vesting.ali_account.1);
+}
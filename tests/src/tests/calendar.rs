@@ -0,0 +1,100 @@
+use vesting_types::{calendar, DripSchedule};
+
+use super::clock::era_clock_config;
+use crate::vesting::{Vesting, VestingConfig};
+
+fn utc_timestamp(year: i64, month: u32, day: u32) -> u64 {
+    calendar::days_from_civil(year, month, day) as u64 * calendar::MS_PER_DAY
+}
+
+#[test]
+fn test_calendar_months_between() {
+    let jan_31 = utc_timestamp(2024, 1, 31);
+    assert_eq!(
+        calendar::months_between(jan_31, utc_timestamp(2024, 2, 28)),
+        0
+    );
+    // Leap year: the 31st falls on February 29th.
+    assert_eq!(
+        calendar::months_between(jan_31, utc_timestamp(2024, 2, 29)),
+        1
+    );
+    assert_eq!(
+        calendar::months_between(jan_31, utc_timestamp(2024, 3, 31) - 1),
+        1
+    );
+    assert_eq!(
+        calendar::months_between(jan_31, utc_timestamp(2024, 3, 31)),
+        2
+    );
+    assert_eq!(
+        calendar::add_months(utc_timestamp(2023, 1, 31), 1),
+        utc_timestamp(2023, 2, 28)
+    );
+    assert_eq!(
+        calendar::add_months(utc_timestamp(2023, 11, 15), 3),
+        utc_timestamp(2024, 2, 15)
+    );
+    assert_eq!(calendar::civil_from_days(0), (1970, 1, 1));
+}
+
+#[test]
+fn test_withdraw_with_monthly_schedule() {
+    let config = VestingConfig {
+        cliff_timestamp: utc_timestamp(2024, 1, 1),
+        drip_schedule: DripSchedule::Monthly,
+        ..Default::default()
+    };
+    let cliff_amount = config.cliff_amount.as_u64();
+    let drip_amount = config.drip_amount.as_u64();
+    let mut vesting = Vesting::deploy_with_config(config);
+    assert_eq!(vesting.get_state().drip_duration, 0);
+    vesting.set_block_time(utc_timestamp(2024, 1, 31));
+    vesting.withdraw(vesting.ali_account.1, cliff_amount);
+    // Drips unlocked on February 1st and March 1st, whatever the length of the months.
+    vesting.set_block_time(utc_timestamp(2024, 3, 1));
+    vesting.withdraw(vesting.ali_account.1, drip_amount * 2);
+    assert_eq!(
+        vesting.get_released_amount(),
+        cliff_amount + drip_amount * 2
+    );
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(6)")]
+fn test_withdraw_before_next_month() {
+    let config = VestingConfig {
+        cliff_timestamp: utc_timestamp(2024, 1, 1),
+        drip_schedule: DripSchedule::Monthly,
+        ..Default::default()
+    };
+    let cliff_amount = config.cliff_amount.as_u64();
+    let drip_amount = config.drip_amount.as_u64();
+    let mut vesting = Vesting::deploy_with_config(config);
+    vesting.set_block_time(utc_timestamp(2024, 2, 1) - 1);
+    vesting.withdraw(vesting.ali_account.1, cliff_amount + drip_amount);
+}
+
+#[test]
+fn test_withdraw_with_quarterly_schedule() {
+    let config = VestingConfig {
+        cliff_timestamp: utc_timestamp(2024, 1, 1),
+        drip_schedule: DripSchedule::Quarterly,
+        ..Default::default()
+    };
+    let cliff_amount = config.cliff_amount.as_u64();
+    let drip_amount = config.drip_amount.as_u64();
+    let mut vesting = Vesting::deploy_with_config(config);
+    vesting.set_block_time(utc_timestamp(2024, 6, 30));
+    vesting.withdraw(vesting.ali_account.1, cliff_amount + drip_amount);
+    assert_eq!(vesting.get_released_amount(), cliff_amount + drip_amount);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(22)")]
+fn test_deploy_calendar_schedule_with_era_clock() {
+    Vesting::deploy_with_config(VestingConfig {
+        drip_schedule: DripSchedule::Monthly,
+        ..era_clock_config()
+    });
+}
@@ -0,0 +1,84 @@
+use casper_types::{runtime_args, ApiError, RuntimeArgs};
+use vesting_types::VestingEvent;
+
+use crate::vesting::{method, Vesting, VestingConfig};
+
+// The default schedule vests its total amount 200 drips after the cliff.
+pub(super) fn claim_deadline_config() -> VestingConfig {
+    let config: VestingConfig = Default::default();
+    VestingConfig {
+        claim_deadline: Some(config.cliff_timestamp + config.drip_duration * 200 + 1_000),
+        ..config
+    }
+}
+
+#[test]
+fn test_sweep_expired() {
+    let config = claim_deadline_config();
+    let claim_deadline = config.claim_deadline.unwrap();
+    let mut vesting = Vesting::deploy_with_config(config);
+    vesting.set_block_time(claim_deadline - 1);
+    vesting.withdraw(vesting.ali_account.1, 300);
+    vesting.set_block_time(claim_deadline);
+    vesting.sweep_expired(vesting.admin_account.1);
+    let state = vesting.get_state();
+    assert_eq!(state.released_amount, state.total_amount);
+    assert_eq!(vesting.get_purse_balance(), 0);
+    assert_eq!(
+        vesting.get_event(0),
+        VestingEvent::Swept {
+            amount: 700.into(),
+            timestamp: claim_deadline
+        }
+    );
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(35)")]
+fn test_sweep_before_claim_deadline() {
+    let config = claim_deadline_config();
+    let claim_deadline = config.claim_deadline.unwrap();
+    let mut vesting = Vesting::deploy_with_config(config);
+    vesting.set_block_time(claim_deadline - 1);
+    vesting.sweep_expired(vesting.admin_account.1);
+}
+
+#[test]
+fn test_sweep_expired_after_pause() {
+    let config = claim_deadline_config();
+    let claim_deadline = config.claim_deadline.unwrap();
+    let cliff_timestamp = config.cliff_timestamp;
+    let mut vesting = Vesting::deploy_with_config(config);
+    vesting.set_block_time(cliff_timestamp);
+    vesting.pause(vesting.admin_account.1);
+    vesting.set_block_time(cliff_timestamp + 5_000);
+    vesting.unpause(vesting.admin_account.1);
+    // The pause moved full vesting, and the deadline with it.
+    vesting.set_block_time(claim_deadline);
+    let error = vesting.call_reverting(
+        vesting.admin_account.1,
+        method::SWEEP_EXPIRED,
+        runtime_args! {},
+    );
+    assert_eq!(error, ApiError::User(35));
+    vesting.set_block_time(claim_deadline + 5_000);
+    vesting.sweep_expired(vesting.admin_account.1);
+    assert_eq!(vesting.get_purse_balance(), 0);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(35)")]
+fn test_sweep_without_claim_deadline() {
+    let mut vesting = Vesting::deploy();
+    vesting.sweep_expired(vesting.admin_account.1);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(34)")]
+fn test_deploy_claim_deadline_before_full_vesting() {
+    let config: VestingConfig = Default::default();
+    Vesting::deploy_with_config(VestingConfig {
+        claim_deadline: Some(config.cliff_timestamp + config.drip_duration * 199),
+        ..config
+    });
+}
@@ -0,0 +1,56 @@
+use vesting_types::ClockSource;
+
+use crate::vesting::{Vesting, VestingConfig};
+
+pub(super) fn era_clock_config() -> VestingConfig {
+    VestingConfig {
+        clock_source: ClockSource::EraId,
+        cliff_timestamp: 2,
+        drip_duration: 1,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_withdraw_with_era_clock() {
+    let config = era_clock_config();
+    let mut vesting = Vesting::deploy_with_config(era_clock_config());
+    vesting.advance_eras(config.cliff_timestamp);
+    vesting.withdraw(vesting.ali_account.1, config.cliff_amount.as_u64());
+    vesting.advance_eras(2);
+    vesting.withdraw(vesting.ali_account.1, (config.drip_amount * 2).as_u64());
+    assert_eq!(
+        vesting.get_released_amount(),
+        (config.cliff_amount + config.drip_amount * 2).as_u64()
+    );
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(6)")]
+fn test_withdraw_before_cliff_era() {
+    let config = era_clock_config();
+    let mut vesting = Vesting::deploy_with_config(era_clock_config());
+    // Block time is past the cliff, but the era is not.
+    vesting.set_block_time(config.cliff_timestamp * 1000);
+    vesting.advance_eras(config.cliff_timestamp - 1);
+    vesting.withdraw(vesting.ali_account.1, config.cliff_amount.as_u64());
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(19)")]
+fn test_deploy_with_block_height_clock() {
+    Vesting::deploy_with_config(VestingConfig {
+        block_height_clock: true,
+        ..Default::default()
+    });
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(21)")]
+fn test_deploy_with_ms_passed_as_eras() {
+    let config: VestingConfig = Default::default();
+    Vesting::deploy_with_config(VestingConfig {
+        cliff_timestamp: config.cliff_timestamp,
+        ..era_clock_config()
+    });
+}
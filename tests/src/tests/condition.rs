@@ -0,0 +1,32 @@
+use crate::vesting::{Vesting, VestingConfig};
+
+fn condition_config() -> VestingConfig {
+    VestingConfig {
+        condition_contract: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_withdraw_allowed_by_condition_contract() {
+    let config = condition_config();
+    let cliff_timestamp = config.cliff_timestamp;
+    let cliff_amount = config.cliff_amount.as_u64();
+    let mut vesting = Vesting::deploy_with_config(config);
+    vesting.allow_withdrawals(vesting.ali_account.1);
+    vesting.set_block_time(cliff_timestamp);
+    vesting.withdraw(vesting.ali_account.1, cliff_amount);
+    assert_eq!(vesting.get_released_amount(), cliff_amount);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(29)")]
+fn test_withdraw_denied_by_condition_contract() {
+    let config = condition_config();
+    let cliff_timestamp = config.cliff_timestamp;
+    let cliff_amount = config.cliff_amount.as_u64();
+    let mut vesting = Vesting::deploy_with_config(config);
+    vesting.allow_withdrawals(vesting.bob_account.1);
+    vesting.set_block_time(cliff_timestamp);
+    vesting.withdraw(vesting.ali_account.1, cliff_amount);
+}
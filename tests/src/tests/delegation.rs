@@ -0,0 +1,188 @@
+use casper_engine_test_support::DEFAULT_UNBONDING_DELAY;
+use casper_types::{runtime_args, system::auction, ApiError, RuntimeArgs, U512};
+
+use crate::vesting::{method, Vesting, VestingConfig};
+
+// Delegations are subject to the auction's minimum delegation amount,
+// so these tests lock up whole CSPR instead of the default config's motes.
+const DELEGATION_TOTAL_AMOUNT: u64 = 1_000_000_000_000;
+pub(super) const DELEGATED_AMOUNT: u64 = 500_000_000_000;
+pub(super) const VALIDATOR_BID_AMOUNT: u64 = 10_000_000_000_000;
+
+pub(super) fn delegation_config() -> VestingConfig {
+    VestingConfig {
+        cliff_amount: DELEGATION_TOTAL_AMOUNT.into(),
+        total_amount: DELEGATION_TOTAL_AMOUNT.into(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_delegate_by_admin() {
+    let mut vesting = Vesting::deploy_with_config(delegation_config());
+    let validator = vesting.bob_account.0.clone();
+    vesting.add_bid(
+        vesting.bob_account.1,
+        validator.clone(),
+        VALIDATOR_BID_AMOUNT,
+    );
+    vesting.delegate(
+        vesting.admin_account.1,
+        vesting.admin_account.0.clone(),
+        validator.clone(),
+        DELEGATED_AMOUNT,
+    );
+    assert_eq!(vesting.get_delegated_amount(), DELEGATED_AMOUNT);
+    assert_eq!(
+        vesting.get_staked_amount(&validator, &vesting.admin_account.0),
+        DELEGATED_AMOUNT
+    );
+    assert_eq!(
+        vesting.get_purse_balance(),
+        DELEGATION_TOTAL_AMOUNT - DELEGATED_AMOUNT
+    );
+}
+
+#[test]
+fn test_undelegate_and_return_stake() {
+    let mut vesting = Vesting::deploy_with_config(delegation_config());
+    let validator = vesting.bob_account.0.clone();
+    vesting.add_bid(
+        vesting.bob_account.1,
+        validator.clone(),
+        VALIDATOR_BID_AMOUNT,
+    );
+    vesting.delegate(
+        vesting.admin_account.1,
+        vesting.admin_account.0.clone(),
+        validator.clone(),
+        DELEGATED_AMOUNT,
+    );
+    vesting.undelegate(
+        vesting.admin_account.1,
+        vesting.admin_account.0.clone(),
+        validator,
+        DELEGATED_AMOUNT,
+    );
+    assert_eq!(vesting.get_delegated_amount(), 0);
+    assert_eq!(vesting.get_unbonding_amount(), DELEGATED_AMOUNT);
+
+    vesting.advance_eras(DEFAULT_UNBONDING_DELAY + 1);
+    vesting.return_stake(vesting.admin_account.1, DELEGATED_AMOUNT);
+    assert_eq!(vesting.get_unbonding_amount(), 0);
+    assert_eq!(vesting.get_purse_balance(), DELEGATION_TOTAL_AMOUNT);
+
+    let config = delegation_config();
+    vesting.set_block_time(config.cliff_timestamp);
+    vesting.withdraw(vesting.ali_account.1, DELEGATION_TOTAL_AMOUNT);
+    assert_eq!(vesting.get_released_amount(), DELEGATION_TOTAL_AMOUNT);
+}
+
+#[test]
+fn test_delegate_by_recipient() {
+    let config = delegation_config();
+    let mut vesting = Vesting::deploy_with_config(delegation_config());
+    let validator = vesting.bob_account.0.clone();
+    vesting.add_bid(
+        vesting.bob_account.1,
+        validator.clone(),
+        VALIDATOR_BID_AMOUNT,
+    );
+    // Without `recipient_can_delegate`, only the admin can delegate.
+    let error = vesting.call_reverting(
+        vesting.ali_account.1,
+        method::DELEGATE,
+        runtime_args! {
+            auction::ARG_DELEGATOR => vesting.ali_account.0.clone(),
+            auction::ARG_VALIDATOR => validator.clone(),
+            auction::ARG_AMOUNT => U512::from(DELEGATED_AMOUNT),
+        },
+    );
+    assert_eq!(error, ApiError::User(14));
+    assert_eq!(
+        vesting.get_staked_amount(&validator, &vesting.ali_account.0),
+        0
+    );
+
+    // Nothing left the purse, so the whole grant can still be released.
+    vesting.pause(vesting.admin_account.1);
+    vesting.set_block_time(config.admin_release_duration);
+    vesting.admin_release(vesting.admin_account.1);
+    assert_eq!(vesting.get_released_amount(), DELEGATION_TOTAL_AMOUNT);
+    assert_eq!(vesting.get_purse_balance(), 0);
+}
+
+#[test]
+fn test_delegate_by_permitted_recipient() {
+    let config = VestingConfig {
+        recipient_can_delegate: true,
+        ..delegation_config()
+    };
+    let mut vesting = Vesting::deploy_with_config(config);
+    let validator = vesting.bob_account.0.clone();
+    vesting.add_bid(
+        vesting.bob_account.1,
+        validator.clone(),
+        VALIDATOR_BID_AMOUNT,
+    );
+    vesting.delegate(
+        vesting.ali_account.1,
+        vesting.ali_account.0.clone(),
+        validator.clone(),
+        DELEGATED_AMOUNT,
+    );
+    assert_eq!(vesting.get_delegated_amount(), DELEGATED_AMOUNT);
+    assert_eq!(
+        vesting.get_staked_amount(&validator, &vesting.ali_account.0),
+        DELEGATED_AMOUNT
+    );
+}
+
+#[test]
+fn test_delegate_vested_funds() {
+    let config = delegation_config();
+    let mut vesting = Vesting::deploy_with_config(delegation_config());
+    let validator = vesting.bob_account.0.clone();
+    vesting.add_bid(
+        vesting.bob_account.1,
+        validator.clone(),
+        VALIDATOR_BID_AMOUNT,
+    );
+    // The whole grant vests at the cliff, leaving nothing the admin can bond.
+    vesting.set_block_time(config.cliff_timestamp);
+    let error = vesting.call_reverting(
+        vesting.admin_account.1,
+        method::DELEGATE,
+        runtime_args! {
+            auction::ARG_DELEGATOR => vesting.admin_account.0.clone(),
+            auction::ARG_VALIDATOR => validator.clone(),
+            auction::ARG_AMOUNT => U512::from(DELEGATED_AMOUNT),
+        },
+    );
+    assert_eq!(error, ApiError::User(6));
+    assert_eq!(
+        vesting.get_staked_amount(&validator, &vesting.admin_account.0),
+        0
+    );
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(16)")]
+fn test_withdraw_delegated_funds() {
+    let config = delegation_config();
+    let mut vesting = Vesting::deploy_with_config(delegation_config());
+    let validator = vesting.bob_account.0.clone();
+    vesting.add_bid(
+        vesting.bob_account.1,
+        validator.clone(),
+        VALIDATOR_BID_AMOUNT,
+    );
+    vesting.delegate(
+        vesting.admin_account.1,
+        vesting.admin_account.0.clone(),
+        validator,
+        DELEGATED_AMOUNT,
+    );
+    vesting.set_block_time(config.cliff_timestamp);
+    vesting.withdraw(vesting.ali_account.1, DELEGATION_TOTAL_AMOUNT);
+}
@@ -0,0 +1,174 @@
+use casper_types::{account::AccountHash, Key, U512};
+use vesting_types::VestingEvent;
+
+use crate::vesting::{Vesting, VestingConfig};
+
+#[test]
+fn test_sweep_excess() {
+    let config: VestingConfig = Default::default();
+    let mut vesting = Vesting::deploy();
+    vesting.set_block_time(config.cliff_timestamp);
+    vesting.withdraw(vesting.ali_account.1, 2);
+    vesting.deposit(vesting.admin_account.1, 300.into());
+    vesting.sweep_excess(vesting.admin_account.1);
+    assert_eq!(vesting.get_purse_balance(), 998);
+    assert_eq!(vesting.get_deposited_amount(vesting.admin_account.1), 1000);
+    assert_eq!(vesting.get_state().deposited_amount, 1000.into());
+    assert_eq!(
+        vesting.get_event(0),
+        VestingEvent::ExcessSwept {
+            amount: 300.into(),
+            timestamp: config.cliff_timestamp
+        }
+    );
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(9)")]
+fn test_sweep_excess_without_excess() {
+    let mut vesting = Vesting::deploy();
+    vesting.sweep_excess(vesting.admin_account.1);
+}
+
+#[test]
+fn test_sweep_excess_then_admin_release_refunds_funders() {
+    let config: VestingConfig = Default::default();
+    let mut vesting = co_funded_vesting();
+    vesting.deposit(vesting.bob_account.1, 200.into());
+    let bob_balance = vesting.get_account_balance(vesting.bob_account.1);
+    vesting.sweep_excess(vesting.admin_account.1);
+    // Bob holds 600 of the 1200 deposited, so half of the surplus is his.
+    assert_eq!(
+        vesting.get_account_balance(vesting.bob_account.1),
+        bob_balance + 100
+    );
+    assert_eq!(vesting.get_deposited_amount(vesting.admin_account.1), 500);
+    assert_eq!(vesting.get_deposited_amount(vesting.bob_account.1), 500);
+    assert_eq!(vesting.get_state().deposited_amount, 1000.into());
+
+    vesting.pause(vesting.admin_account.1);
+    vesting.set_block_time(config.admin_release_duration);
+    vesting.admin_release(vesting.admin_account.1);
+    assert_eq!(
+        vesting.get_account_balance(vesting.bob_account.1),
+        bob_balance + 600
+    );
+    assert_eq!(vesting.get_purse_balance(), 0);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(47)")]
+fn test_deposit_from_too_many_funders() {
+    let mut vesting = co_funded_vesting();
+    // The admin and Bob are the first two of the 16 funders allowed.
+    for seed in 10..24 {
+        let funder = vesting.create_funded_account(seed);
+        vesting.deposit(funder, 1.into());
+    }
+    assert_eq!(vesting.get_state().funder_count, 16);
+    let funder = vesting.create_funded_account(24);
+    vesting.deposit(funder, 1.into());
+}
+
+fn co_funded_vesting() -> Vesting {
+    let mut vesting = Vesting::deploy_with_config(VestingConfig {
+        fund_on_deploy: false,
+        ..Default::default()
+    });
+    vesting.deposit(vesting.admin_account.1, 600.into());
+    vesting.deposit(vesting.bob_account.1, 300.into());
+    vesting.deposit(vesting.bob_account.1, 100.into());
+    vesting
+}
+
+#[test]
+fn test_deposits_recorded_per_funder() {
+    let vesting = co_funded_vesting();
+    let state = vesting.get_state();
+    assert_eq!(
+        vesting.get_funders(),
+        vec![vesting.admin_account.1, vesting.bob_account.1]
+    );
+    assert_eq!(state.deposited_amount, 1000.into());
+    assert_eq!(vesting.get_deposited_amount(vesting.admin_account.1), 600);
+    assert_eq!(vesting.get_deposited_amount(vesting.bob_account.1), 400);
+    assert_eq!(vesting.get_deposited_amount(vesting.ali_account.1), 0);
+    assert_eq!(vesting.get_purse_balance(), 1000);
+}
+
+#[test]
+fn test_deposit_purse_reused() {
+    let mut vesting = co_funded_vesting();
+    let purse = vesting
+        .get_deposit_purse(vesting.bob_account.1)
+        .expect("should have deposit purse.");
+    vesting.deposit(vesting.bob_account.1, 50.into());
+    assert_eq!(
+        vesting.get_deposit_purse(vesting.bob_account.1),
+        Some(purse)
+    );
+    // The contract takes everything deposited into it.
+    assert_eq!(vesting.builder.get_purse_balance(purse), U512::zero());
+    assert_eq!(vesting.get_deposited_amount(vesting.bob_account.1), 450);
+}
+
+#[test]
+fn test_admin_share_refunded_to_release_destination() {
+    let config: VestingConfig = Default::default();
+    let destination = AccountHash::new([8u8; 32]);
+    let mut vesting = Vesting::deploy_with_config(VestingConfig {
+        fund_on_deploy: false,
+        release_destination: Some(Key::Account(destination)),
+        ..Default::default()
+    });
+    vesting.deposit(vesting.admin_account.1, 600.into());
+    vesting.deposit(vesting.bob_account.1, 400.into());
+    vesting.pause(vesting.admin_account.1);
+    vesting.set_block_time(config.admin_release_duration);
+    vesting.admin_release(vesting.admin_account.1);
+    // The admin's share goes where the admin sends releases, not back to the admin's account.
+    assert_eq!(vesting.get_account_balance(destination), 600);
+    assert_eq!(vesting.last_transfer_ids().len(), 2);
+}
+
+#[test]
+fn test_admin_release_refunds_funders() {
+    let config: VestingConfig = Default::default();
+    let mut vesting = co_funded_vesting();
+    vesting.pause(vesting.admin_account.1);
+    vesting.set_block_time(config.admin_release_duration);
+    let bob_balance = vesting.get_account_balance(vesting.bob_account.1);
+    vesting.admin_release(vesting.admin_account.1);
+    assert_eq!(
+        vesting.get_account_balance(vesting.bob_account.1),
+        bob_balance + 400
+    );
+    assert_eq!(vesting.get_purse_balance(), 0);
+    // Bob's refund is made first, before the admin's share goes to the release destination.
+    let transfer_ids = vesting.last_transfer_ids();
+    assert_eq!(
+        vesting.get_event(0),
+        VestingEvent::Refunded {
+            funder: vesting.bob_account.1,
+            amount: 400.into(),
+            transfer_id: transfer_ids[0],
+            timestamp: config.admin_release_duration
+        }
+    );
+}
+
+#[test]
+fn test_partial_reclaim_refunds_funders() {
+    let config: VestingConfig = Default::default();
+    let mut vesting = co_funded_vesting();
+    vesting.pause(vesting.admin_account.1);
+    vesting.set_block_time(config.admin_release_duration);
+    let bob_balance = vesting.get_account_balance(vesting.bob_account.1);
+    vesting.admin_release_amount(vesting.admin_account.1, 99);
+    // Bob's share is rounded down, the remainder goes to the admin.
+    assert_eq!(
+        vesting.get_account_balance(vesting.bob_account.1),
+        bob_balance + 39
+    );
+    assert_eq!(vesting.get_purse_balance(), 901);
+}
@@ -0,0 +1,94 @@
+use casper_types::{account::AccountHash, runtime_args, Key, RuntimeArgs, U512};
+use vesting_types::VestingEvent;
+
+use crate::vesting::{method, proxy_key, Vesting, VestingConfig};
+
+fn fee_config(fee_collector: AccountHash) -> VestingConfig {
+    VestingConfig {
+        fee_bps: 250,
+        fee_collector: Some(fee_collector),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_withdraw_with_fee() {
+    let config: VestingConfig = Default::default();
+    let fee_collector = AccountHash::new([9u8; 32]);
+    let mut vesting = Vesting::deploy_with_config(fee_config(fee_collector));
+    let full_vesting_time = config.cliff_timestamp + config.drip_duration * 200;
+    vesting.set_block_time(full_vesting_time);
+    vesting.withdraw(vesting.ali_account.1, 999);
+    // 2.5% of 999 is 24.975, the fee is rounded down.
+    assert_eq!(vesting.get_account_balance(fee_collector), 24);
+    assert_eq!(vesting.get_state().fees_paid, 24.into());
+    assert_eq!(vesting.get_released_amount(), 999);
+    assert_eq!(vesting.get_purse_balance(), 1);
+    // The fee is sent before the payout.
+    let transfer_ids = vesting.last_transfer_ids();
+    assert_eq!(
+        vesting.get_event(0),
+        VestingEvent::FeeCharged {
+            amount: 24.into(),
+            fees_paid: 24.into(),
+            transfer_id: transfer_ids[0],
+            timestamp: full_vesting_time
+        }
+    );
+}
+
+#[test]
+fn test_admin_release_with_fee() {
+    let config: VestingConfig = Default::default();
+    let fee_collector = AccountHash::new([9u8; 32]);
+    let destination = AccountHash::new([8u8; 32]);
+    let mut vesting = Vesting::deploy_with_config(VestingConfig {
+        release_destination: Some(Key::Account(destination)),
+        ..fee_config(fee_collector)
+    });
+    vesting.pause(vesting.admin_account.1);
+    vesting.set_block_time(config.admin_release_duration);
+    // 2.5% of the 400 reclaimed, then of the 600 left.
+    vesting.admin_release_amount(vesting.admin_account.1, 400);
+    assert_eq!(vesting.get_account_balance(fee_collector), 10);
+    assert_eq!(vesting.get_account_balance(destination), 390);
+    vesting.admin_release(vesting.admin_account.1);
+    assert_eq!(vesting.get_account_balance(fee_collector), 25);
+    assert_eq!(vesting.get_account_balance(destination), 975);
+    assert_eq!(vesting.get_state().fees_paid, 25.into());
+    assert_eq!(vesting.get_purse_balance(), 0);
+}
+
+#[test]
+fn test_withdraw_with_fee_through_proxy() {
+    let config: VestingConfig = Default::default();
+    let fee_collector = AccountHash::new([9u8; 32]);
+    let mut vesting = Vesting::deploy_with_config(fee_config(fee_collector));
+    vesting.set_block_time(config.cliff_timestamp + config.drip_duration * 200);
+    vesting.call_proxy(
+        vesting.ali_account.1,
+        method::WITHDRAW,
+        runtime_args! {
+            "amount" => U512::from(999)
+        },
+    );
+    // The recipient gets what is left after the fee of 24.
+    let withdrawn: U512 = vesting.query_account(vesting.ali_account.1, proxy_key::WITHDRAWN_AMOUNT);
+    assert_eq!(withdrawn, U512::from(975));
+    vesting.call_proxy(
+        vesting.admin_account.1,
+        method::GET_FEES_PAID,
+        runtime_args! {},
+    );
+    let fees_paid: U512 = vesting.query_account(vesting.admin_account.1, proxy_key::FEES_PAID);
+    assert_eq!(fees_paid, U512::from(24));
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(46)")]
+fn test_fee_without_collector() {
+    Vesting::deploy_with_config(VestingConfig {
+        fee_bps: 250,
+        ..Default::default()
+    });
+}
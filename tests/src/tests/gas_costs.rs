@@ -0,0 +1,105 @@
+use casper_engine_test_support::DEFAULT_UNBONDING_DELAY;
+use casper_types::Key;
+
+use super::{
+    acceptance::acceptance_config,
+    claim_deadline::claim_deadline_config,
+    delegation::{delegation_config, DELEGATED_AMOUNT, VALIDATOR_BID_AMOUNT},
+    milestones::milestone_config,
+    pause_modes::forfeiting_config,
+};
+use crate::{
+    gas,
+    vesting::{Vesting, VestingConfig},
+};
+
+#[test]
+fn test_gas_costs_within_baseline() {
+    let config: VestingConfig = Default::default();
+    let mut costs = Vec::new();
+
+    let mut vesting = Vesting::deploy();
+    let admin = vesting.admin_account.1;
+    let recipient = vesting.ali_account.1;
+    let bob = vesting.bob_account.1;
+    costs.push(("deposit", vesting.deposit(admin, 10.into())));
+    costs.push(("sweep_excess", vesting.sweep_excess(admin)));
+    vesting.set_block_time(config.cliff_timestamp);
+    costs.push(("withdraw", vesting.withdraw(recipient, 1)));
+    costs.push((
+        "set_payout_splits",
+        vesting.set_payout_splits(recipient, vec![(bob, 1_000)]),
+    ));
+    costs.push(("accelerate", vesting.accelerate(admin, 5)));
+    let cost = vesting.propose_release_destination(admin, Key::Account(bob));
+    costs.push(("propose_release_destination", cost));
+    let cost = vesting.confirm_release_destination(admin, Key::Account(bob));
+    costs.push(("confirm_release_destination", cost));
+    costs.push(("pause", vesting.pause(admin)));
+    costs.push(("unpause", vesting.unpause(admin)));
+    vesting.pause(admin);
+    vesting.set_block_time(config.cliff_timestamp + config.admin_release_duration);
+    costs.push((
+        "admin_release_amount",
+        vesting.admin_release_amount(admin, 100),
+    ));
+    costs.push(("admin_release", vesting.admin_release(admin)));
+
+    let mut vesting = Vesting::deploy();
+    vesting.set_block_time(config.cliff_timestamp + config.drip_duration * 200);
+    vesting.withdraw(recipient, config.total_amount.as_u64());
+    costs.push(("close", vesting.close(admin)));
+
+    let mut vesting = Vesting::deploy_with_config(milestone_config());
+    costs.push(("complete_milestone", vesting.complete_milestone(admin, 0)));
+
+    let config = claim_deadline_config();
+    let claim_deadline = config.claim_deadline.unwrap();
+    let mut vesting = Vesting::deploy_with_config(config);
+    vesting.set_block_time(claim_deadline);
+    costs.push(("sweep_expired", vesting.sweep_expired(admin)));
+
+    let config = forfeiting_config();
+    let (cliff_timestamp, drip_duration) = (config.cliff_timestamp, config.drip_duration);
+    let mut vesting = Vesting::deploy_with_config(config);
+    vesting.set_block_time(cliff_timestamp);
+    vesting.pause(admin);
+    vesting.set_block_time(cliff_timestamp + drip_duration * 20);
+    vesting.unpause(admin);
+    costs.push(("reclaim_forfeited", vesting.reclaim_forfeited(admin)));
+
+    let mut vesting = Vesting::deploy_with_config(acceptance_config());
+    costs.push((
+        "accept_grant",
+        vesting.accept_grant(recipient, Some([1u8; 32])),
+    ));
+    let mut vesting = Vesting::deploy_with_config(acceptance_config());
+    costs.push(("cancel_grant", vesting.cancel_grant(admin)));
+
+    let mut vesting = Vesting::deploy();
+    costs.push(("migrate", vesting.migrate(admin)));
+
+    let mut vesting = Vesting::deploy_with_config(delegation_config());
+    let validator = vesting.bob_account.0.clone();
+    let delegator = vesting.admin_account.0.clone();
+    vesting.add_bid(bob, validator.clone(), VALIDATOR_BID_AMOUNT);
+    let cost = vesting.delegate(
+        admin,
+        delegator.clone(),
+        validator.clone(),
+        DELEGATED_AMOUNT,
+    );
+    costs.push(("delegate", cost));
+    costs.push((
+        "undelegate",
+        vesting.undelegate(admin, delegator, validator, DELEGATED_AMOUNT),
+    ));
+    vesting.advance_eras(DEFAULT_UNBONDING_DELAY + 1);
+    // A deposit stands in for the stake returned by the session, one mote over it counting as
+    // rewards, so that the entry point is measured without the transfer into its purse.
+    vesting.deposit(admin, (DELEGATED_AMOUNT + 1).into());
+    let cost = vesting.settle_unbonded(admin, DELEGATED_AMOUNT + 1);
+    costs.push(("settle_unbonded", cost));
+
+    gas::check_against_baseline(&costs);
+}
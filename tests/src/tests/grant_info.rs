@@ -0,0 +1,48 @@
+use std::collections::BTreeMap;
+
+use crate::vesting::{Vesting, VestingConfig};
+
+#[test]
+fn test_grant_info() {
+    let mut metadata = BTreeMap::new();
+    metadata.insert("title".to_string(), "Engineer".to_string());
+    metadata.insert("department".to_string(), "Research".to_string());
+    let vesting = Vesting::deploy_with_config(VestingConfig {
+        terms_hash: Some([9u8; 32]),
+        grant_id: Some("GRANT-0042".to_string()),
+        metadata: metadata.clone(),
+        ..Default::default()
+    });
+    let grant_info = vesting.get_grant_info();
+    assert_eq!(grant_info.terms_hash, Some([9u8; 32]));
+    assert_eq!(grant_info.grant_id, Some("GRANT-0042".to_string()));
+    assert_eq!(grant_info.metadata, metadata);
+    assert_eq!(vesting.get_grant("GRANT-0042"), Some(vesting.contract_hash));
+    assert_eq!(vesting.get_grant("GRANT-0043"), None);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(48)")]
+fn test_deploy_metadata_too_large() {
+    let mut metadata = BTreeMap::new();
+    metadata.insert("notes".to_string(), "x".repeat(1020));
+    Vesting::deploy_with_config(VestingConfig {
+        metadata,
+        ..Default::default()
+    });
+}
+
+#[test]
+fn test_grant_info_not_set() {
+    let vesting = Vesting::deploy();
+    assert_eq!(vesting.get_grant_info(), Default::default());
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(40)")]
+fn test_deploy_grant_id_too_long() {
+    Vesting::deploy_with_config(VestingConfig {
+        grant_id: Some("x".repeat(65)),
+        ..Default::default()
+    });
+}
@@ -0,0 +1,133 @@
+use casper_types::{runtime_args, ApiError, RuntimeArgs};
+use vesting_types::{Lifecycle, VestingEvent};
+
+use super::acceptance::acceptance_config;
+use crate::vesting::{method, Vesting, VestingConfig};
+
+#[test]
+fn test_lifecycle_pause_and_unpause() {
+    let mut vesting = Vesting::deploy();
+    assert_eq!(vesting.get_state().lifecycle, Lifecycle::Active);
+    vesting.pause(vesting.admin_account.1);
+    assert_eq!(vesting.get_state().lifecycle, Lifecycle::Paused);
+    vesting.unpause(vesting.admin_account.1);
+    assert_eq!(vesting.get_state().lifecycle, Lifecycle::Active);
+}
+
+#[test]
+fn test_lifecycle_pending_until_accepted() {
+    let mut vesting = Vesting::deploy_with_config(acceptance_config());
+    assert_eq!(vesting.get_state().lifecycle, Lifecycle::Pending);
+    vesting.accept_grant(vesting.ali_account.1, None);
+    assert_eq!(vesting.get_state().lifecycle, Lifecycle::Active);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(43)")]
+fn test_pause_pending_grant() {
+    let mut vesting = Vesting::deploy_with_config(acceptance_config());
+    vesting.pause(vesting.admin_account.1);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(43)")]
+fn test_unpause_after_admin_release() {
+    let config: VestingConfig = Default::default();
+    let mut vesting = Vesting::deploy();
+    vesting.pause(vesting.admin_account.1);
+    vesting.set_block_time(config.admin_release_duration);
+    vesting.admin_release(vesting.admin_account.1);
+    assert_eq!(vesting.get_state().lifecycle, Lifecycle::Revoked);
+    vesting.unpause(vesting.admin_account.1);
+}
+
+#[test]
+fn test_close() {
+    let config: VestingConfig = Default::default();
+    let total_amount = config.total_amount;
+    let full_vesting_time = config.cliff_timestamp + config.drip_duration * 200;
+    let mut vesting = Vesting::deploy();
+    vesting.set_block_time(full_vesting_time);
+    vesting.withdraw(vesting.ali_account.1, total_amount.as_u64());
+    // Dust sent to the purse after the last withdrawal.
+    vesting.deposit(vesting.admin_account.1, 5.into());
+    vesting.close(vesting.admin_account.1);
+    assert_eq!(vesting.get_state().lifecycle, Lifecycle::Completed);
+    assert_eq!(vesting.get_purse_balance(), 0);
+    assert_eq!(
+        vesting.get_event(0),
+        VestingEvent::Closed {
+            dust: 5.into(),
+            timestamp: full_vesting_time
+        }
+    );
+}
+
+#[test]
+fn test_close_paused_grant() {
+    let config: VestingConfig = Default::default();
+    let mut vesting = Vesting::deploy();
+    vesting.set_block_time(config.cliff_timestamp + config.drip_duration * 200);
+    vesting.withdraw(vesting.ali_account.1, config.total_amount.as_u64());
+    vesting.pause(vesting.admin_account.1);
+    vesting.close(vesting.admin_account.1);
+    let state = vesting.get_state();
+    assert_eq!(state.lifecycle, Lifecycle::Completed);
+    assert!(!state.is_paused);
+}
+
+#[test]
+fn test_close_revoked_grant() {
+    let config: VestingConfig = Default::default();
+    let mut vesting = Vesting::deploy();
+    vesting.pause(vesting.admin_account.1);
+    vesting.set_block_time(config.admin_release_duration);
+    vesting.admin_release(vesting.admin_account.1);
+    // Nothing came in since the release, so nothing is swept.
+    let error = vesting.call_reverting(
+        vesting.admin_account.1,
+        method::SWEEP_EXCESS,
+        runtime_args! {},
+    );
+    assert_eq!(error, ApiError::User(9));
+    vesting.close(vesting.admin_account.1);
+    let state = vesting.get_state();
+    assert_eq!(state.lifecycle, Lifecycle::Revoked);
+    assert!(!state.is_paused);
+}
+
+#[test]
+fn test_stake_return_purse_of_revoked_grant() {
+    let config: VestingConfig = Default::default();
+    let mut vesting = Vesting::deploy();
+    vesting.pause(vesting.admin_account.1);
+    vesting.set_block_time(config.admin_release_duration);
+    vesting.admin_release(vesting.admin_account.1);
+    let error = vesting.call_reverting(
+        vesting.admin_account.1,
+        method::GET_STAKE_RETURN_PURSE,
+        runtime_args! {},
+    );
+    assert_eq!(error, ApiError::User(43));
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(43)")]
+fn test_close_unfinished_grant() {
+    let config: VestingConfig = Default::default();
+    let mut vesting = Vesting::deploy();
+    vesting.set_block_time(config.cliff_timestamp);
+    vesting.close(vesting.admin_account.1);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(43)")]
+fn test_pause_after_close() {
+    let config: VestingConfig = Default::default();
+    let total_amount = config.total_amount.as_u64();
+    let mut vesting = Vesting::deploy();
+    vesting.set_block_time(config.cliff_timestamp + config.drip_duration * 200);
+    vesting.withdraw(vesting.ali_account.1, total_amount);
+    vesting.close(vesting.admin_account.1);
+    vesting.pause(vesting.admin_account.1);
+}
@@ -0,0 +1,98 @@
+use vesting_types::{MilestoneStatus, VestingEvent};
+
+use crate::vesting::{Vesting, VestingConfig};
+
+pub(super) fn milestone_config() -> VestingConfig {
+    VestingConfig {
+        milestones: vec![
+            ("audit".to_string(), 400.into()),
+            ("mainnet launch".to_string(), 600.into()),
+        ],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_withdraw_completed_milestone() {
+    let mut vesting = Vesting::deploy_with_config(milestone_config());
+    vesting.complete_milestone(vesting.admin_account.1, 0);
+    vesting.withdraw(vesting.ali_account.1, 400);
+    let milestones = vesting.get_milestones();
+    assert_eq!(milestones[0].status, MilestoneStatus::Completed);
+    assert_eq!(milestones[1].status, MilestoneStatus::Pending);
+    assert_eq!(vesting.get_state().completed_milestones_amount, 400.into());
+    assert_eq!(
+        vesting.get_event(0),
+        VestingEvent::MilestoneCompleted {
+            id: 0,
+            timestamp: 0
+        }
+    );
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(6)")]
+fn test_withdraw_pending_milestone() {
+    let mut vesting = Vesting::deploy_with_config(milestone_config());
+    vesting.complete_milestone(vesting.admin_account.1, 0);
+    vesting.withdraw(vesting.ali_account.1, 401);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(3)")]
+fn test_complete_milestone_by_not_admin() {
+    let mut vesting = Vesting::deploy_with_config(milestone_config());
+    vesting.complete_milestone(vesting.ali_account.1, 0);
+}
+
+#[test]
+fn test_admin_release_expired_milestone() {
+    let config: VestingConfig = Default::default();
+    let deadline = config.cliff_timestamp;
+    let mut vesting = Vesting::deploy_with_config(VestingConfig {
+        milestone_deadlines: vec![0, deadline],
+        ..milestone_config()
+    });
+    vesting.set_block_time(deadline);
+    vesting.admin_release(vesting.admin_account.1);
+    let state = vesting.get_state();
+    assert_eq!(
+        vesting.get_milestones()[1].status,
+        MilestoneStatus::Reclaimed
+    );
+    assert_eq!(state.reclaimed_amount, 600.into());
+    assert_eq!(vesting.get_purse_balance(), 400);
+    assert_eq!(
+        vesting.get_event(0),
+        VestingEvent::MilestoneReclaimed {
+            id: 1,
+            amount: 600.into(),
+            timestamp: deadline
+        }
+    );
+    // The other milestone still vests.
+    vesting.complete_milestone(vesting.admin_account.1, 0);
+    vesting.withdraw(vesting.ali_account.1, 400);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(8)")]
+fn test_admin_release_milestone_before_deadline() {
+    let config: VestingConfig = Default::default();
+    let deadline = config.cliff_timestamp;
+    let mut vesting = Vesting::deploy_with_config(VestingConfig {
+        milestone_deadlines: vec![deadline, deadline],
+        ..milestone_config()
+    });
+    vesting.set_block_time(deadline - 1);
+    vesting.admin_release(vesting.admin_account.1);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(25)")]
+fn test_deploy_milestones_not_matching_total() {
+    Vesting::deploy_with_config(VestingConfig {
+        total_amount: 999.into(),
+        ..milestone_config()
+    });
+}
@@ -0,0 +1,43 @@
+use vesting_types::VestingEvent;
+
+use crate::vesting::{Vesting, VestingConfig};
+
+#[test]
+fn test_partial_admin_release() {
+    let config: VestingConfig = Default::default();
+    let mut vesting = Vesting::deploy();
+    vesting.pause(vesting.admin_account.1);
+    vesting.set_block_time(config.admin_release_duration);
+    vesting.admin_release_amount(vesting.admin_account.1, 600);
+    let state = vesting.get_state();
+    assert_eq!(state.reclaimed_amount, 600.into());
+    assert!(state.released_amount.is_zero());
+    assert_eq!(state.total_amount, config.total_amount);
+    assert_eq!(vesting.get_purse_balance(), 400);
+    assert_eq!(
+        vesting.get_event(0),
+        VestingEvent::Reclaimed {
+            amount: 600.into(),
+            timestamp: config.admin_release_duration
+        }
+    );
+    // The rest of the schedule vests the remaining 400, by its original end.
+    vesting.unpause(vesting.admin_account.1);
+    let cliff_timestamp = config.cliff_timestamp + config.admin_release_duration;
+    // 502 of 1000 scheduled, so 200 of the remaining 400.
+    vesting.set_block_time(cliff_timestamp + config.drip_duration * 100);
+    vesting.withdraw(vesting.ali_account.1, 200);
+    vesting.set_block_time(cliff_timestamp + config.drip_duration * 200);
+    vesting.withdraw(vesting.ali_account.1, 200);
+    assert_eq!(vesting.get_purse_balance(), 0);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(33)")]
+fn test_partial_admin_release_more_than_unvested() {
+    let config: VestingConfig = Default::default();
+    let mut vesting = Vesting::deploy();
+    vesting.pause(vesting.admin_account.1);
+    vesting.set_block_time(config.admin_release_duration);
+    vesting.admin_release_amount(vesting.admin_account.1, config.total_amount.as_u64() + 1);
+}
@@ -0,0 +1,134 @@
+use vesting_types::{PauseMode, VestingEvent};
+
+use crate::vesting::{Vesting, VestingConfig};
+
+#[test]
+#[should_panic(expected = "ApiError::User(36)")]
+fn test_withdraw_during_strict_pause() {
+    let config = VestingConfig {
+        strict_pause: true,
+        ..Default::default()
+    };
+    let cliff_timestamp = config.cliff_timestamp;
+    let cliff_amount = config.cliff_amount.as_u64();
+    let mut vesting = Vesting::deploy_with_config(config);
+    vesting.set_block_time(cliff_timestamp);
+    vesting.pause(vesting.admin_account.1);
+    vesting.withdraw(vesting.ali_account.1, cliff_amount);
+}
+
+#[test]
+fn test_withdraw_after_strict_pause() {
+    let config = VestingConfig {
+        strict_pause: true,
+        ..Default::default()
+    };
+    let cliff_timestamp = config.cliff_timestamp;
+    let cliff_amount = config.cliff_amount.as_u64();
+    let mut vesting = Vesting::deploy_with_config(config);
+    assert!(vesting.get_state().strict_pause);
+    vesting.set_block_time(cliff_timestamp);
+    vesting.pause(vesting.admin_account.1);
+    vesting.unpause(vesting.admin_account.1);
+    vesting.withdraw(vesting.ali_account.1, cliff_amount);
+    assert_eq!(vesting.get_released_amount(), cliff_amount);
+}
+
+pub(super) fn forfeiting_config() -> VestingConfig {
+    VestingConfig {
+        pause_mode: PauseMode::Forfeiting,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_forfeiting_pause() {
+    let config = forfeiting_config();
+    let cliff_timestamp = config.cliff_timestamp;
+    let drip_duration = config.drip_duration;
+    let mut vesting = Vesting::deploy_with_config(config);
+    // 52 vested when paused, 152 scheduled when unpaused.
+    vesting.set_block_time(cliff_timestamp + drip_duration * 10);
+    vesting.pause(vesting.admin_account.1);
+    assert_eq!(
+        vesting.get_state().available_amount(vesting.current_time),
+        52.into()
+    );
+    vesting.set_block_time(cliff_timestamp + drip_duration * 30);
+    assert_eq!(
+        vesting.get_state().available_amount(vesting.current_time),
+        52.into()
+    );
+    vesting.unpause(vesting.admin_account.1);
+    let state = vesting.get_state();
+    assert_eq!(state.forfeited_amount, 100.into());
+    assert_eq!(state.granted_amount(), 900.into());
+    assert_eq!(
+        vesting.get_event(0),
+        VestingEvent::Forfeited {
+            amount: 100.into(),
+            timestamp: cliff_timestamp + drip_duration * 30
+        }
+    );
+    // The schedule keeps its dates: the next 10 drips vest 50 more.
+    vesting.set_block_time(cliff_timestamp + drip_duration * 40);
+    vesting.withdraw(vesting.ali_account.1, 102);
+    vesting.set_block_time(cliff_timestamp + drip_duration * 200);
+    assert_eq!(
+        vesting.get_state().available_amount(vesting.current_time),
+        798.into()
+    );
+}
+
+#[test]
+fn test_reclaim_forfeited() {
+    let config = forfeiting_config();
+    let cliff_timestamp = config.cliff_timestamp;
+    let drip_duration = config.drip_duration;
+    let mut vesting = Vesting::deploy_with_config(config);
+    vesting.set_block_time(cliff_timestamp);
+    vesting.pause(vesting.admin_account.1);
+    vesting.set_block_time(cliff_timestamp + drip_duration * 20);
+    vesting.unpause(vesting.admin_account.1);
+    vesting.reclaim_forfeited(vesting.admin_account.1);
+    let state = vesting.get_state();
+    assert_eq!(state.forfeited_amount, 0.into());
+    assert_eq!(state.reclaimed_amount, 100.into());
+    assert_eq!(vesting.get_purse_balance(), 900);
+    vesting.set_block_time(cliff_timestamp + drip_duration * 200);
+    vesting.withdraw(vesting.ali_account.1, 900);
+    assert_eq!(vesting.get_purse_balance(), 0);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(9)")]
+fn test_reclaim_forfeited_without_forfeiture() {
+    let config = forfeiting_config();
+    let cliff_timestamp = config.cliff_timestamp;
+    let mut vesting = Vesting::deploy_with_config(config);
+    vesting.set_block_time(cliff_timestamp);
+    vesting.pause(vesting.admin_account.1);
+    vesting.unpause(vesting.admin_account.1);
+    vesting.reclaim_forfeited(vesting.admin_account.1);
+}
+
+#[test]
+fn test_admin_release_with_forfeited_amount() {
+    let config = forfeiting_config();
+    let cliff_timestamp = config.cliff_timestamp;
+    let drip_duration = config.drip_duration;
+    let admin_release_duration = config.admin_release_duration;
+    let mut vesting = Vesting::deploy_with_config(config);
+    vesting.set_block_time(cliff_timestamp);
+    vesting.pause(vesting.admin_account.1);
+    vesting.set_block_time(cliff_timestamp + drip_duration * 20);
+    vesting.unpause(vesting.admin_account.1);
+    vesting.pause(vesting.admin_account.1);
+    vesting.set_block_time(cliff_timestamp + drip_duration * 20 + admin_release_duration);
+    vesting.admin_release(vesting.admin_account.1);
+    let state = vesting.get_state();
+    assert_eq!(state.forfeited_amount, 0.into());
+    assert_eq!(state.reclaimed_amount, 100.into());
+    assert_eq!(state.released_amount, 900.into());
+    assert_eq!(vesting.get_purse_balance(), 0);
+}
@@ -0,0 +1,70 @@
+use casper_types::account::AccountHash;
+use vesting_types::{PayoutSplit, VestingEvent};
+
+use crate::vesting::{Vesting, VestingConfig};
+
+#[test]
+fn test_withdraw_with_payout_splits() {
+    let config: VestingConfig = Default::default();
+    let tax_account = AccountHash::new([7u8; 32]);
+    let fund_account = AccountHash::new([8u8; 32]);
+    let mut vesting = Vesting::deploy_with_config(VestingConfig {
+        payout_splits: vec![(tax_account, 2_500), (fund_account, 1_000)],
+        ..Default::default()
+    });
+    vesting.set_block_time(config.cliff_timestamp + config.drip_duration * 200);
+    vesting.withdraw(vesting.ali_account.1, 999);
+    // Shares are rounded down, the recipient gets the remainder.
+    assert_eq!(vesting.get_account_balance(tax_account), 249);
+    assert_eq!(vesting.get_account_balance(fund_account), 99);
+    assert_eq!(vesting.get_released_amount(), 999);
+    assert_eq!(vesting.get_purse_balance(), 1);
+}
+
+#[test]
+fn test_set_payout_splits() {
+    let config: VestingConfig = Default::default();
+    let mut vesting = Vesting::deploy();
+    let payout_splits = vec![(vesting.bob_account.1, 5_000)];
+    vesting.set_payout_splits(vesting.ali_account.1, payout_splits);
+    assert_eq!(
+        vesting.get_payout_splits(),
+        vec![PayoutSplit {
+            target: vesting.bob_account.1,
+            basis_points: 5_000
+        }]
+    );
+    assert_eq!(
+        vesting.get_event(0),
+        VestingEvent::PayoutSplitsChanged {
+            payout_splits: vesting.get_payout_splits(),
+            timestamp: 0
+        }
+    );
+    vesting.set_block_time(config.cliff_timestamp);
+    let bob_balance = vesting.get_account_balance(vesting.bob_account.1);
+    vesting.withdraw(vesting.ali_account.1, 2);
+    assert_eq!(
+        vesting.get_account_balance(vesting.bob_account.1),
+        bob_balance + 1
+    );
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(4)")]
+fn test_set_payout_splits_by_admin() {
+    let mut vesting = Vesting::deploy();
+    let payout_splits = vec![(vesting.admin_account.1, 5_000)];
+    vesting.set_payout_splits(vesting.admin_account.1, payout_splits);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(45)")]
+fn test_payout_splits_above_whole_amount() {
+    let mut vesting = Vesting::deploy();
+    let payout_splits = vec![
+        (vesting.bob_account.1, 6_000),
+        (vesting.admin_account.1, 4_001),
+    ];
+    vesting.set_payout_splits(vesting.ali_account.1, payout_splits);
+}
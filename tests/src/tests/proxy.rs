@@ -0,0 +1,162 @@
+use casper_types::{runtime_args, RuntimeArgs, U512};
+use vesting_types::Lifecycle;
+
+use super::{
+    acceptance::acceptance_config, claim_deadline::claim_deadline_config,
+    milestones::milestone_config, pause_modes::forfeiting_config,
+};
+use crate::vesting::{method, proxy_key, Vesting, VestingConfig};
+
+#[test]
+fn test_withdraw_through_proxy() {
+    let config: VestingConfig = Default::default();
+    let mut vesting = Vesting::deploy();
+    vesting.set_block_time(config.cliff_timestamp);
+    vesting.call_proxy(
+        vesting.ali_account.1,
+        method::WITHDRAW,
+        runtime_args! {
+            "amount" => U512::from(2),
+            "transfer_id" => 42u64
+        },
+    );
+    assert_eq!(vesting.get_released_amount(), 2);
+    assert_eq!(vesting.get_state().last_transfer_id, Some(42));
+    let withdrawn: U512 = vesting.query_account(vesting.ali_account.1, proxy_key::WITHDRAWN_AMOUNT);
+    assert_eq!(withdrawn, U512::from(2));
+}
+
+#[test]
+fn test_pause_and_admin_release_through_proxy() {
+    let config: VestingConfig = Default::default();
+    let mut vesting = Vesting::deploy();
+    vesting.call_proxy(vesting.admin_account.1, method::PAUSE, runtime_args! {});
+    assert!(vesting.get_pause_status());
+    vesting.set_block_time(config.admin_release_duration);
+    vesting.call_proxy(
+        vesting.admin_account.1,
+        method::ADMIN_RELEASE,
+        runtime_args! {},
+    );
+    let released: U512 =
+        vesting.query_account(vesting.admin_account.1, proxy_key::ADMIN_RELEASED_AMOUNT);
+    assert_eq!(released, config.total_amount);
+}
+
+#[test]
+fn test_partial_admin_release_through_proxy() {
+    let config: VestingConfig = Default::default();
+    let mut vesting = Vesting::deploy();
+    vesting.pause(vesting.admin_account.1);
+    vesting.set_block_time(config.admin_release_duration);
+    vesting.call_proxy(
+        vesting.admin_account.1,
+        method::ADMIN_RELEASE,
+        runtime_args! {
+            "amount" => U512::from(600)
+        },
+    );
+    let released: U512 =
+        vesting.query_account(vesting.admin_account.1, proxy_key::ADMIN_RELEASED_AMOUNT);
+    assert_eq!(released, U512::from(600));
+    let state = vesting.get_state();
+    assert_eq!(state.reclaimed_amount, 600.into());
+    assert_eq!(state.lifecycle, Lifecycle::Paused);
+    assert_eq!(vesting.get_purse_balance(), 400);
+}
+
+#[test]
+fn test_accept_grant_and_accelerate_through_proxy() {
+    let mut vesting = Vesting::deploy_with_config(acceptance_config());
+    vesting.call_proxy(
+        vesting.ali_account.1,
+        method::ACCEPT_GRANT,
+        runtime_args! {
+            "agreement_hash" => [1u8; 32]
+        },
+    );
+    assert_eq!(vesting.get_state().agreement_hash, Some([1u8; 32]));
+    vesting.call_proxy(
+        vesting.admin_account.1,
+        method::ACCELERATE,
+        runtime_args! {
+            "percentage" => 50u8
+        },
+    );
+    let accelerated: U512 =
+        vesting.query_account(vesting.admin_account.1, proxy_key::ACCELERATED_AMOUNT);
+    assert_eq!(accelerated, U512::from(500));
+}
+
+#[test]
+fn test_cancel_grant_through_proxy() {
+    let mut vesting = Vesting::deploy_with_config(acceptance_config());
+    vesting.call_proxy(
+        vesting.admin_account.1,
+        method::CANCEL_GRANT,
+        runtime_args! {},
+    );
+    let cancelled: U512 =
+        vesting.query_account(vesting.admin_account.1, proxy_key::CANCELLED_AMOUNT);
+    assert_eq!(cancelled, U512::from(1000));
+}
+
+#[test]
+fn test_complete_milestone_through_proxy() {
+    let mut vesting = Vesting::deploy_with_config(milestone_config());
+    vesting.call_proxy(
+        vesting.admin_account.1,
+        method::COMPLETE_MILESTONE,
+        runtime_args! {
+            "id" => 0u32
+        },
+    );
+    assert_eq!(vesting.get_state().completed_milestones_amount, 400.into());
+}
+
+#[test]
+fn test_sweep_excess_and_close_through_proxy() {
+    let config: VestingConfig = Default::default();
+    let mut vesting = Vesting::deploy();
+    let admin = vesting.admin_account.1;
+    vesting.set_block_time(config.cliff_timestamp + config.drip_duration * 200);
+    vesting.withdraw(vesting.ali_account.1, config.total_amount.as_u64());
+    vesting.deposit(admin, 300.into());
+    vesting.call_proxy(admin, method::SWEEP_EXCESS, runtime_args! {});
+    let swept: U512 = vesting.query_account(admin, proxy_key::SWEPT_EXCESS_AMOUNT);
+    assert_eq!(swept, U512::from(300));
+    vesting.deposit(admin, 5.into());
+    vesting.call_proxy(admin, method::CLOSE, runtime_args! {});
+    let dust: U512 = vesting.query_account(admin, proxy_key::CLOSED_AMOUNT);
+    assert_eq!(dust, U512::from(5));
+}
+
+#[test]
+fn test_sweep_expired_and_reclaim_forfeited_through_proxy() {
+    let config = claim_deadline_config();
+    let claim_deadline = config.claim_deadline.unwrap();
+    let mut vesting = Vesting::deploy_with_config(config);
+    let admin = vesting.admin_account.1;
+    vesting.set_block_time(claim_deadline);
+    vesting.call_proxy(admin, method::SWEEP_EXPIRED, runtime_args! {});
+    let swept: U512 = vesting.query_account(admin, proxy_key::SWEPT_EXPIRED_AMOUNT);
+    assert_eq!(swept, U512::from(1000));
+
+    let config = forfeiting_config();
+    let (cliff_timestamp, drip_duration) = (config.cliff_timestamp, config.drip_duration);
+    let mut vesting = Vesting::deploy_with_config(config);
+    vesting.set_block_time(cliff_timestamp);
+    vesting.pause(admin);
+    vesting.set_block_time(cliff_timestamp + drip_duration * 20);
+    vesting.unpause(admin);
+    vesting.call_proxy(admin, method::RECLAIM_FORFEITED, runtime_args! {});
+    let reclaimed: U512 = vesting.query_account(admin, proxy_key::RECLAIMED_FORFEITED_AMOUNT);
+    assert_eq!(reclaimed, U512::from(100));
+}
+
+#[test]
+#[should_panic(expected = "ApiError::InvalidArgument")]
+fn test_proxy_unknown_method() {
+    let mut vesting = Vesting::deploy();
+    vesting.call_proxy(vesting.admin_account.1, "delete", runtime_args! {});
+}
@@ -0,0 +1,64 @@
+use casper_types::{account::AccountHash, Key};
+use vesting_types::ReleaseDestination;
+
+use crate::vesting::{Vesting, VestingConfig};
+
+#[test]
+fn test_admin_release_to_release_destination() {
+    let config: VestingConfig = Default::default();
+    let mut vesting = Vesting::deploy();
+    let treasury = vesting.bob_account.1;
+    vesting.propose_release_destination(vesting.admin_account.1, Key::Account(treasury));
+    vesting.confirm_release_destination(vesting.admin_account.1, Key::Account(treasury));
+    assert_eq!(
+        vesting.get_state().release_destination,
+        Some(ReleaseDestination::Account(treasury))
+    );
+    let treasury_balance = vesting.get_account_balance(treasury);
+    vesting.pause(vesting.admin_account.1);
+    vesting.set_block_time(config.admin_release_duration);
+    vesting.admin_release(vesting.admin_account.1);
+    assert_eq!(
+        vesting.get_account_balance(treasury),
+        treasury_balance + config.total_amount.as_u64()
+    );
+}
+
+#[test]
+fn test_release_destination_set_at_deploy() {
+    let treasury = AccountHash::new([7u8; 32]);
+    let vesting = Vesting::deploy_with_config(VestingConfig {
+        release_destination: Some(Key::Account(treasury)),
+        ..Default::default()
+    });
+    assert_eq!(
+        vesting.get_state().release_destination,
+        Some(ReleaseDestination::Account(treasury))
+    );
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(31)")]
+fn test_confirm_release_destination_without_proposal() {
+    let mut vesting = Vesting::deploy();
+    let treasury = Key::Account(vesting.bob_account.1);
+    vesting.confirm_release_destination(vesting.admin_account.1, treasury);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(32)")]
+fn test_confirm_other_release_destination() {
+    let mut vesting = Vesting::deploy();
+    let treasury = Key::Account(vesting.bob_account.1);
+    vesting.propose_release_destination(vesting.admin_account.1, treasury);
+    let other = Key::Account(vesting.ali_account.1);
+    vesting.confirm_release_destination(vesting.admin_account.1, other);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(3)")]
+fn test_propose_release_destination_by_not_admin() {
+    let mut vesting = Vesting::deploy();
+    let treasury = Key::Account(vesting.bob_account.1);
+    vesting.propose_release_destination(vesting.ali_account.1, treasury);
+}
@@ -0,0 +1,76 @@
+use vesting_types::StartTrigger;
+
+use crate::vesting::{Vesting, VestingConfig, CLIFF_OFFSET};
+
+#[test]
+fn test_start_on_install() {
+    let config = VestingConfig {
+        start_trigger: StartTrigger::Install,
+        ..Default::default()
+    };
+    let cliff_amount = config.cliff_amount.as_u64();
+    let mut vesting = Vesting::deploy_with_config(config);
+    let state = vesting.get_state();
+    // The install runs at block time 0.
+    assert_eq!(state.start_timestamp, Some(0));
+    assert_eq!(state.cliff_timestamp, CLIFF_OFFSET);
+    vesting.set_block_time(CLIFF_OFFSET);
+    vesting.withdraw(vesting.ali_account.1, cliff_amount);
+    assert_eq!(vesting.get_released_amount(), cliff_amount);
+}
+
+#[test]
+fn test_start_on_funding() {
+    let config = VestingConfig {
+        start_trigger: StartTrigger::Funding,
+        fund_on_deploy: false,
+        ..Default::default()
+    };
+    let cliff_amount = config.cliff_amount.as_u64();
+    let total_amount = config.total_amount;
+    let funding_time = 1_700_000_000_000;
+    let mut vesting = Vesting::deploy_with_config(config);
+    assert!(vesting.get_state().is_start_pending());
+    vesting.set_block_time(funding_time);
+    // Half of the grant isn't enough to start.
+    vesting.deposit(vesting.admin_account.1, total_amount / 2);
+    assert!(vesting.get_state().is_start_pending());
+    vesting.deposit(vesting.admin_account.1, total_amount / 2);
+    let state = vesting.get_state();
+    assert_eq!(state.start_timestamp, Some(funding_time));
+    assert_eq!(state.cliff_timestamp, funding_time + CLIFF_OFFSET);
+    vesting.set_block_time(funding_time + CLIFF_OFFSET - 1);
+    assert_eq!(
+        vesting.get_state().available_amount(vesting.current_time),
+        0.into()
+    );
+    vesting.set_block_time(funding_time + CLIFF_OFFSET);
+    vesting.withdraw(vesting.ali_account.1, cliff_amount);
+    assert_eq!(vesting.get_released_amount(), cliff_amount);
+}
+
+#[test]
+fn test_start_on_acceptance() {
+    let config = VestingConfig {
+        start_trigger: StartTrigger::Acceptance,
+        requires_acceptance: true,
+        ..Default::default()
+    };
+    let acceptance_time = 1_700_000_000_000;
+    let mut vesting = Vesting::deploy_with_config(config);
+    assert!(vesting.get_state().is_start_pending());
+    vesting.set_block_time(acceptance_time);
+    vesting.accept_grant(vesting.ali_account.1, None);
+    let state = vesting.get_state();
+    assert_eq!(state.start_timestamp, Some(acceptance_time));
+    assert_eq!(state.cliff_timestamp, acceptance_time + CLIFF_OFFSET);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(42)")]
+fn test_deploy_start_on_acceptance_without_acceptance() {
+    Vesting::deploy_with_config(VestingConfig {
+        start_trigger: StartTrigger::Acceptance,
+        ..Default::default()
+    });
+}
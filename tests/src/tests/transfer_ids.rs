@@ -0,0 +1,78 @@
+use casper_types::account::AccountHash;
+
+use crate::vesting::{Vesting, VestingConfig};
+
+#[test]
+fn test_withdraw_with_transfer_id() {
+    let config: VestingConfig = Default::default();
+    let mut vesting = Vesting::deploy();
+    vesting.set_block_time(config.cliff_timestamp);
+    vesting.withdraw_with_transfer_id(vesting.ali_account.1, 2, 42);
+    let state = vesting.get_state();
+    assert_eq!(state.last_transfer_id, Some(42));
+    // The deposit made on deploy was the first transfer.
+    assert_eq!(state.transfer_count, 2);
+}
+
+#[test]
+fn test_deposit_with_transfer_id() {
+    let mut vesting = Vesting::deploy();
+    vesting.deposit_with_transfer_id(vesting.admin_account.1, 5.into(), 7);
+    assert_eq!(vesting.get_state().last_transfer_id, Some(7));
+    // The session moves the motes into its purse under an id of its own.
+    let transfer_ids = vesting.last_transfer_ids();
+    assert_eq!(transfer_ids.len(), 2);
+    assert_ne!(transfer_ids[0], 7);
+    assert_eq!(transfer_ids[1], 7);
+}
+
+#[test]
+fn test_refund_without_main_transfer() {
+    let config: VestingConfig = Default::default();
+    let mut vesting = Vesting::deploy_with_config(VestingConfig {
+        fund_on_deploy: false,
+        ..Default::default()
+    });
+    vesting.deposit(vesting.bob_account.1, 1000.into());
+    let deposit_transfer_id = vesting.get_state().last_transfer_id;
+    vesting.pause(vesting.admin_account.1);
+    vesting.set_block_time(config.admin_release_duration);
+    vesting.admin_release(vesting.admin_account.1);
+    // Everything went back to Bob, nothing to the release destination.
+    assert_eq!(vesting.last_transfer_ids().len(), 1);
+    assert_eq!(vesting.get_state().last_transfer_id, deposit_transfer_id);
+}
+
+#[test]
+fn test_transfer_ids_derived_per_transfer() {
+    let config: VestingConfig = Default::default();
+    let tax_account = AccountHash::new([7u8; 32]);
+    let mut vesting = Vesting::deploy_with_config(VestingConfig {
+        payout_splits: vec![(tax_account, 5_000)],
+        ..Default::default()
+    });
+    vesting.set_block_time(config.cliff_timestamp + config.drip_duration);
+    vesting.withdraw(vesting.ali_account.1, 4);
+    let first_ids = vesting.last_transfer_ids();
+    assert_eq!(first_ids.len(), 2);
+    assert_ne!(first_ids[0], first_ids[1]);
+    // The payout to the recipient is the main transfer, made last.
+    assert_eq!(vesting.get_state().last_transfer_id, Some(first_ids[1]));
+    assert_ne!(first_ids[1], 1);
+    vesting.withdraw(vesting.ali_account.1, 2);
+    let second_ids = vesting.last_transfer_ids();
+    assert!(second_ids.iter().all(|id| !first_ids.contains(id)));
+}
+
+#[test]
+fn test_transfer_ids_differ_between_grants() {
+    let first = Vesting::deploy();
+    let second = Vesting::deploy();
+    // Both funded on deploy, in the first transfer of each grant.
+    assert_eq!(first.get_state().transfer_count, 1);
+    assert_eq!(second.get_state().transfer_count, 1);
+    assert_ne!(
+        first.get_state().last_transfer_id,
+        second.get_state().last_transfer_id
+    );
+}
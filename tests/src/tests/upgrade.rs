@@ -0,0 +1,95 @@
+use casper_types::{runtime_args, ApiError, RuntimeArgs, U512};
+use vesting_types::Lifecycle;
+
+use crate::vesting::{method, Layout0, Vesting, VestingConfig};
+
+#[test]
+fn test_upgrade_keeps_balances() {
+    let config: VestingConfig = Default::default();
+    let mut vesting = Vesting::deploy();
+    vesting.set_block_time(config.cliff_timestamp);
+    vesting.withdraw(vesting.ali_account.1, 1);
+    let previous_contract_hash = vesting.contract_hash;
+
+    vesting.upgrade(vesting.admin_account.1);
+    assert_ne!(vesting.contract_hash, previous_contract_hash);
+    assert_eq!(vesting.get_total_amount(), config.total_amount.as_u64());
+    assert_eq!(vesting.get_released_amount(), 1);
+    assert_eq!(
+        vesting.get_purse_balance(),
+        config.total_amount.as_u64() - 1
+    );
+
+    vesting.withdraw(vesting.ali_account.1, 1);
+    assert_eq!(vesting.get_released_amount(), 2);
+}
+
+#[test]
+#[should_panic(expected = "ApiError::User(13)")]
+fn test_upgrade_by_not_installer() {
+    let mut vesting = Vesting::deploy();
+    vesting.upgrade(vesting.ali_account.1);
+}
+
+#[test]
+fn test_upgrade_from_layout_0() {
+    let config: VestingConfig = Default::default();
+    let mut vesting = Vesting::deploy_with_config(VestingConfig {
+        layout_0: Some(Default::default()),
+        ..Default::default()
+    });
+    vesting.upgrade(vesting.admin_account.1);
+    let state = vesting.get_state();
+    assert_eq!(state.admin, vesting.admin_account.1);
+    assert_eq!(state.recipient, vesting.ali_account.1);
+    assert_eq!(state.cliff_timestamp, config.cliff_timestamp);
+    assert_eq!(state.drip_duration, config.drip_duration);
+    assert_eq!(state.total_amount, config.total_amount);
+    assert_eq!(state.lifecycle, Lifecycle::Active);
+    assert!(vesting.query_contract::<U512>("total_amount").is_none());
+    assert_eq!(vesting.get_purse_balance(), config.total_amount.as_u64());
+
+    vesting.set_block_time(config.cliff_timestamp);
+    vesting.withdraw(vesting.ali_account.1, 2);
+    assert_eq!(vesting.get_released_amount(), 2);
+    assert_eq!(
+        vesting.get_purse_balance(),
+        config.total_amount.as_u64() - 2
+    );
+}
+
+#[test]
+fn test_upgrade_paused_layout_0() {
+    let config: VestingConfig = Default::default();
+    let paused_at = config.cliff_timestamp - 1_000;
+    let mut vesting = Vesting::deploy_with_config(VestingConfig {
+        layout_0: Some(Layout0 {
+            released_amount: 1.into(),
+            is_paused: true,
+            last_pause_timestamp: paused_at,
+            on_pause_duration: 500,
+        }),
+        ..Default::default()
+    });
+    vesting.upgrade(vesting.admin_account.1);
+    let state = vesting.get_state();
+    assert_eq!(state.lifecycle, Lifecycle::Paused);
+    assert_eq!(state.last_pause_timestamp, paused_at);
+    assert_eq!(state.on_pause_duration, 500);
+    assert_eq!(state.released_amount, U512::from(1));
+
+    // The migrated pause keeps shifting the schedule.
+    vesting.set_block_time(config.cliff_timestamp);
+    vesting.unpause(vesting.admin_account.1);
+    assert_eq!(vesting.get_state().on_pause_duration, 1_500);
+    vesting.set_block_time(config.cliff_timestamp + 1_500);
+    vesting.withdraw(vesting.ali_account.1, 1);
+    assert_eq!(vesting.get_released_amount(), 2);
+}
+
+#[test]
+fn test_migrate_by_not_admin() {
+    let mut vesting = Vesting::deploy();
+    let error = vesting.call_reverting(vesting.bob_account.1, method::MIGRATE, runtime_args! {});
+    assert_eq!(error, ApiError::User(3));
+}
@@ -1,56 +1,200 @@
-use std::path::PathBuf;
+use std::{collections::BTreeMap, path::PathBuf};
 
 use casper_engine_test_support::{
     DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, WasmTestBuilder, ARG_AMOUNT,
     DEFAULT_ACCOUNT_ADDR, DEFAULT_PAYMENT, DEFAULT_RUN_GENESIS_REQUEST,
 };
+use casper_execution_engine::core::{engine_state, execution};
 use casper_execution_engine::storage::global_state::in_memory::InMemoryGlobalState;
 use casper_types::bytesrepr::FromBytes;
-use casper_types::system::mint;
+use casper_types::system::{auction, mint};
 use casper_types::{account::AccountHash, runtime_args, PublicKey, RuntimeArgs, SecretKey, U512};
-use casper_types::{CLTyped, ContractHash, Key};
+use casper_types::{ApiError, CLTyped, ContractHash, Key, StoredValue, URef};
 use rand::Rng;
+use vesting_types::{
+    ClockSource, DripSchedule, GrantInfo, Milestone, PauseMode, PayoutSplit, StartTrigger,
+    VestingEvent, VestingState,
+};
 
 mod arg {
     pub const ADMIN: &str = "admin";
     pub const RECIPIENT: &str = "recipient";
     pub const CLIFF_TIME: &str = "cliff_timestamp";
+    pub const CLIFF_OFFSET: &str = "cliff_offset";
+    pub const START_TRIGGER: &str = "start_trigger";
     pub const CLIFF_AMOUNT: &str = "cliff_amount";
     pub const DRIP_DURATION: &str = "drip_duration";
     pub const DRIP_AMOUNT: &str = "drip_amount";
     pub const TOTAL_AMOUNT: &str = "total_amount";
-    pub const PAUSE_FLAG: &str = "is_paused";
-    pub const RELEASED_AMOUNT: &str = "released_amount";
     pub const ADMIN_RELEASE_DURATION: &str = "admin_release_duration";
     pub const DEPOSIT_CONTRACT_HASH: &str = "deposit_contract_hash";
+    pub const VESTING_CONTRACT_HASH: &str = "vesting_contract_hash";
     pub const AMOUNT: &str = "amount";
+    pub const UPGRADE: &str = "upgrade";
+    pub const METHOD: &str = "method";
+    pub const RECIPIENT_CAN_DELEGATE: &str = "recipient_can_delegate";
+    pub const CLOCK_SOURCE: &str = "clock_source";
+    pub const DRIP_SCHEDULE: &str = "drip_schedule";
+    pub const PERCENTAGE: &str = "percentage";
+    pub const MILESTONES: &str = "milestones";
+    pub const MILESTONE_DEADLINES: &str = "milestone_deadlines";
+    pub const ID: &str = "id";
+    pub const CONDITION_CONTRACT: &str = "condition_contract";
+    pub const ACCOUNT: &str = "account";
+    pub const RELEASE_DESTINATION: &str = "release_destination";
+    pub const CLAIM_DEADLINE: &str = "claim_deadline";
+    pub const STRICT_PAUSE: &str = "strict_pause";
+    pub const PAUSE_MODE: &str = "pause_mode";
+    pub const REQUIRES_ACCEPTANCE: &str = "requires_acceptance";
+    pub const AGREEMENT_HASH: &str = "agreement_hash";
+    pub const TERMS_HASH: &str = "terms_hash";
+    pub const GRANT_ID: &str = "grant_id";
+    pub const METADATA: &str = "metadata";
+    pub const PAYOUT_SPLITS: &str = "payout_splits";
+    pub const FEE_BPS: &str = "fee_bps";
+    pub const FEE_COLLECTOR: &str = "fee_collector";
+    pub const TRANSFER_ID: &str = "transfer_id";
+    pub const RELEASED_AMOUNT: &str = "released_amount";
+    pub const IS_PAUSED: &str = "is_paused";
+    pub const LAST_PAUSE_TIMESTAMP: &str = "last_pause_timestamp_ms";
+    pub const ON_PAUSE_DURATION: &str = "on_pause_duration_ms";
 }
 
-mod method {
+pub mod method {
     pub const WITHDRAW: &str = "withdraw";
     pub const PAUSE: &str = "pause";
     pub const UNPAUSE: &str = "unpause";
     pub const ADMIN_RELEASE: &str = "admin_release";
+    pub const DELEGATE: &str = "delegate";
+    pub const UNDELEGATE: &str = "undelegate";
+    pub const ACCELERATE: &str = "accelerate";
+    pub const COMPLETE_MILESTONE: &str = "complete_milestone";
+    pub const ALLOW: &str = "allow";
+    pub const PROPOSE_RELEASE_DESTINATION: &str = "propose_release_destination";
+    pub const CONFIRM_RELEASE_DESTINATION: &str = "confirm_release_destination";
+    pub const SWEEP_EXPIRED: &str = "sweep_expired";
+    pub const RECLAIM_FORFEITED: &str = "reclaim_forfeited";
+    pub const ACCEPT_GRANT: &str = "accept_grant";
+    pub const CANCEL_GRANT: &str = "cancel_grant";
+    pub const CLOSE: &str = "close";
+    pub const SWEEP_EXCESS: &str = "sweep_excess";
+    pub const SET_PAYOUT_SPLITS: &str = "set_payout_splits";
+    pub const MIGRATE: &str = "migrate";
+    pub const GET_FEES_PAID: &str = "get_fees_paid";
+    pub const GET_STAKE_RETURN_PURSE: &str = "get_stake_return_purse";
+    pub const SETTLE_UNBONDED: &str = "settle_unbonded";
+}
+
+pub mod proxy_key {
+    pub const WITHDRAWN_AMOUNT: &str = "vesting_withdrawn_amount";
+    pub const ADMIN_RELEASED_AMOUNT: &str = "vesting_admin_released_amount";
+    pub const FEES_PAID: &str = "vesting_fees_paid";
+    pub const ACCELERATED_AMOUNT: &str = "vesting_accelerated_amount";
+    pub const CANCELLED_AMOUNT: &str = "vesting_cancelled_amount";
+    pub const CLOSED_AMOUNT: &str = "vesting_closed_amount";
+    pub const SWEPT_EXPIRED_AMOUNT: &str = "vesting_swept_expired_amount";
+    pub const SWEPT_EXCESS_AMOUNT: &str = "vesting_swept_excess_amount";
+    pub const RECLAIMED_FORFEITED_AMOUNT: &str = "vesting_reclaimed_forfeited_amount";
+}
+
+mod key {
+    pub const PURSE_NAME: &str = "vesting_main_purse";
+    pub const STATE: &str = "vesting_state";
+    pub const EVENTS: &str = "events";
+    pub const EVENT_COUNT: &str = "event_count";
+    pub const DEPOSITS: &str = "deposits";
+    pub const DEPOSIT_PURSE: &str = "vesting_deposit_purse";
+    pub const FUNDERS: &str = "funders";
+    pub const MILESTONES: &str = "milestones";
+    pub const PAYOUT_SPLITS: &str = "payout_splits";
+    pub const CONDITION_CONTRACT_HASH: &str = "condition_contract_hash";
+    pub const GRANT_INFO: &str = "grant_info";
+    pub const GRANTS: &str = "vesting_grants";
+}
+
+// Named keys of layout 0 that its mock doesn't take from the rest of the config.
+#[derive(Default)]
+pub struct Layout0 {
+    pub released_amount: U512,
+    pub is_paused: bool,
+    pub last_pause_timestamp: u64,
+    pub on_pause_duration: u64,
 }
 
+// Time from the start trigger to the cliff, passed instead of `cliff_timestamp` when
+// `start_trigger` isn't absolute.
+pub const CLIFF_OFFSET: u64 = 1_000;
+
+// Times are in the unit of `clock_source`: milliseconds for block time, eras for the era id.
 pub struct VestingConfig {
-    pub cliff_timestamp: U512,
+    pub cliff_timestamp: u64,
     pub cliff_amount: U512,
-    pub drip_duration: U512,
+    pub drip_duration: u64,
     pub drip_amount: U512,
     pub total_amount: U512,
-    pub admin_release_duration: U512,
+    pub admin_release_duration: u64,
+    pub recipient_can_delegate: bool,
+    pub clock_source: ClockSource,
+    pub drip_schedule: DripSchedule,
+    // Names and amounts of the milestones, and their deadlines if not empty, 0 for none.
+    pub milestones: Vec<(String, U512)>,
+    pub milestone_deadlines: Vec<u64>,
+    // Install the mock condition contract and gate withdrawals with it.
+    pub condition_contract: bool,
+    pub release_destination: Option<Key>,
+    pub claim_deadline: Option<u64>,
+    pub strict_pause: bool,
+    pub pause_mode: PauseMode,
+    pub requires_acceptance: bool,
+    pub terms_hash: Option<[u8; 32]>,
+    pub grant_id: Option<String>,
+    pub metadata: BTreeMap<String, String>,
+    pub start_trigger: StartTrigger,
+    // Deposit `total_amount` right after the install.
+    pub fund_on_deploy: bool,
+    // Target accounts and basis points of the payout splits.
+    pub payout_splits: Vec<(AccountHash, u16)>,
+    pub fee_bps: u16,
+    pub fee_collector: Option<AccountHash>,
+    // Pass block time arguments in seconds instead of milliseconds.
+    pub time_args_in_secs: bool,
+    // Install layout 0 with its mock, holding `total_amount`, instead of the contract.
+    pub layout_0: Option<Layout0>,
+    // Pass `clock_source` 1, set aside for block height, instead of `clock_source`.
+    pub block_height_clock: bool,
 }
 
 impl Default for VestingConfig {
     fn default() -> VestingConfig {
         VestingConfig {
-            cliff_timestamp: 10.into(),
+            cliff_timestamp: 1_700_000_000_000,
             cliff_amount: 2.into(),
-            drip_duration: 3.into(),
+            drip_duration: 3,
             drip_amount: 5.into(),
             total_amount: 1000.into(),
-            admin_release_duration: 123.into(),
+            admin_release_duration: 123,
+            recipient_can_delegate: false,
+            clock_source: ClockSource::BlockTime,
+            drip_schedule: DripSchedule::Fixed,
+            milestones: Vec::new(),
+            milestone_deadlines: Vec::new(),
+            condition_contract: false,
+            release_destination: None,
+            claim_deadline: None,
+            strict_pause: false,
+            pause_mode: PauseMode::Shifting,
+            requires_acceptance: false,
+            terms_hash: None,
+            grant_id: None,
+            metadata: BTreeMap::new(),
+            start_trigger: StartTrigger::Absolute,
+            fund_on_deploy: true,
+            payout_splits: Vec::new(),
+            fee_bps: 0,
+            fee_collector: None,
+            time_args_in_secs: false,
+            layout_0: None,
+            block_height_clock: false,
         }
     }
 }
@@ -58,7 +202,7 @@ impl Default for VestingConfig {
 pub struct Vesting {
     pub builder: WasmTestBuilder<InMemoryGlobalState>,
     pub contract_hash: ContractHash,
-    // pub package_hash: ContractPackageHash,
+    pub condition_contract_hash: Option<ContractHash>,
     pub admin_account: (PublicKey, AccountHash),
     pub ali_account: (PublicKey, AccountHash),
     pub bob_account: (PublicKey, AccountHash),
@@ -67,6 +211,10 @@ pub struct Vesting {
 
 impl Vesting {
     pub fn deploy() -> Self {
+        Self::deploy_with_config(Default::default())
+    }
+
+    pub fn deploy_with_config(config: VestingConfig) -> Self {
         let mut rng = rand::thread_rng();
         let admin_public_key: PublicKey =
             (&SecretKey::ed25519_from_bytes([1u8; 32]).unwrap()).into();
@@ -76,29 +224,97 @@ impl Vesting {
         let ali_account_addr = AccountHash::from(&ali_public_key);
         let bob_account_addr = AccountHash::from(&bob_public_key);
 
-        let mut code = PathBuf::from("contract.wasm");
-        let config: VestingConfig = Default::default();
+        let code = if config.layout_0.is_some() {
+            PathBuf::from("layout_0_mock.wasm")
+        } else {
+            PathBuf::from("contract.wasm")
+        };
+        let clock_source = if config.block_height_clock {
+            1u8
+        } else {
+            config.clock_source as u8
+        };
         let mut args = runtime_args! {
             arg::ADMIN => admin_account_addr,
             arg::RECIPIENT => ali_account_addr,
-            arg::CLIFF_TIME => config.cliff_timestamp,
             arg::CLIFF_AMOUNT => config.cliff_amount,
-            arg::DRIP_DURATION => config.drip_duration,
             arg::DRIP_AMOUNT => config.drip_amount,
             arg::TOTAL_AMOUNT => config.total_amount,
-            arg::ADMIN_RELEASE_DURATION => config.admin_release_duration
+            arg::RECIPIENT_CAN_DELEGATE => config.recipient_can_delegate,
+            arg::CLOCK_SOURCE => clock_source,
+            arg::DRIP_SCHEDULE => config.drip_schedule as u8,
+            arg::STRICT_PAUSE => config.strict_pause,
+            arg::PAUSE_MODE => config.pause_mode as u8,
+            arg::REQUIRES_ACCEPTANCE => config.requires_acceptance,
+            arg::START_TRIGGER => config.start_trigger as u8
         };
+        let (unit_suffix, unit_ms) = match config.clock_source {
+            ClockSource::EraId => ("_eras", 1),
+            _ if config.time_args_in_secs => ("_secs", 1000),
+            _ => ("_ms", 1),
+        };
+        let cliff = if config.start_trigger == StartTrigger::Absolute {
+            (arg::CLIFF_TIME, config.cliff_timestamp)
+        } else {
+            (arg::CLIFF_OFFSET, CLIFF_OFFSET)
+        };
+        for (name, time) in [
+            cliff,
+            (arg::DRIP_DURATION, config.drip_duration),
+            (arg::ADMIN_RELEASE_DURATION, config.admin_release_duration),
+        ] {
+            args.insert(format!("{}{}", name, unit_suffix), time / unit_ms)
+                .unwrap();
+        }
+        if let Some(claim_deadline) = config.claim_deadline {
+            let name = format!("{}{}", arg::CLAIM_DEADLINE, unit_suffix);
+            args.insert(name, claim_deadline / unit_ms).unwrap();
+        }
+        if let Some(layout_0) = config.layout_0 {
+            args.insert(arg::RELEASED_AMOUNT, layout_0.released_amount)
+                .unwrap();
+            args.insert(arg::IS_PAUSED, layout_0.is_paused).unwrap();
+            args.insert(arg::LAST_PAUSE_TIMESTAMP, layout_0.last_pause_timestamp)
+                .unwrap();
+            args.insert(arg::ON_PAUSE_DURATION, layout_0.on_pause_duration)
+                .unwrap();
+        }
+        if let Some(release_destination) = config.release_destination {
+            args.insert(arg::RELEASE_DESTINATION, release_destination)
+                .unwrap();
+        }
+        if let Some(terms_hash) = config.terms_hash {
+            args.insert(arg::TERMS_HASH, terms_hash).unwrap();
+        }
+        if let Some(grant_id) = config.grant_id {
+            args.insert(arg::GRANT_ID, grant_id).unwrap();
+        }
+        if !config.metadata.is_empty() {
+            args.insert(arg::METADATA, config.metadata).unwrap();
+        }
+        if !config.milestones.is_empty() {
+            args.insert(arg::MILESTONES, config.milestones).unwrap();
+        }
+        if config.fee_bps != 0 {
+            args.insert(arg::FEE_BPS, config.fee_bps).unwrap();
+        }
+        if let Some(fee_collector) = config.fee_collector {
+            args.insert(arg::FEE_COLLECTOR, fee_collector).unwrap();
+        }
+        if !config.payout_splits.is_empty() {
+            args.insert(arg::PAYOUT_SPLITS, config.payout_splits)
+                .unwrap();
+        }
+        if !config.milestone_deadlines.is_empty() {
+            let deadlines: Vec<u64> = config
+                .milestone_deadlines
+                .iter()
+                .map(|deadline| deadline / unit_ms)
+                .collect();
+            let name = format!("{}{}", arg::MILESTONE_DEADLINES, unit_suffix);
+            args.insert(name, deadlines).unwrap();
+        }
 
-        let mut deploy = DeployItemBuilder::new()
-            .with_empty_payment_bytes(runtime_args! {ARG_AMOUNT => *DEFAULT_PAYMENT})
-            .with_session_code(code, args)
-            .with_address(admin_account_addr)
-            .with_authorization_keys(&[admin_account_addr])
-            .with_deploy_hash(rng.gen())
-            .build();
-        let mut execute_request = ExecuteRequestBuilder::from_deploy_item(deploy)
-            .with_block_time(0)
-            .build();
         let mut builder = InMemoryWasmTestBuilder::default();
         builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST).commit();
 
@@ -162,43 +378,138 @@ impl Vesting {
             .expect_success()
             .commit();
 
-        builder.exec(execute_request).expect_success().commit();
-
-        let contract_hash = builder
-            .query(
-                None,
-                Key::Account(admin_account_addr),
-                &["vesting_contract_hash".to_string()],
-            )
-            .expect("should be stored value.")
-            .as_cl_value()
-            .expect("should be cl value.")
-            .clone()
-            .into_t()
-            .expect("should be string.");
-        code = PathBuf::from("deposit.wasm");
-        args = runtime_args! {
-            arg::DEPOSIT_CONTRACT_HASH => contract_hash,
-            arg::AMOUNT => config.total_amount,
+        let condition_contract_hash = if config.condition_contract {
+            let deploy = DeployItemBuilder::new()
+                .with_empty_payment_bytes(runtime_args! {ARG_AMOUNT => *DEFAULT_PAYMENT})
+                .with_session_code(PathBuf::from("condition_mock.wasm"), runtime_args! {})
+                .with_address(admin_account_addr)
+                .with_authorization_keys(&[admin_account_addr])
+                .with_deploy_hash(rng.gen())
+                .build();
+            let execute_request = ExecuteRequestBuilder::from_deploy_item(deploy).build();
+            builder.exec(execute_request).expect_success().commit();
+            let condition_contract_hash: ContractHash = builder
+                .query(
+                    None,
+                    Key::Account(admin_account_addr),
+                    &[key::CONDITION_CONTRACT_HASH.to_string()],
+                )
+                .expect("should be stored value.")
+                .as_cl_value()
+                .expect("should be cl value.")
+                .clone()
+                .into_t()
+                .expect("should be contract hash.");
+            args.insert(arg::CONDITION_CONTRACT, condition_contract_hash)
+                .unwrap();
+            Some(condition_contract_hash)
+        } else {
+            None
         };
-        deploy = DeployItemBuilder::new()
+
+        let deploy = DeployItemBuilder::new()
             .with_empty_payment_bytes(runtime_args! {ARG_AMOUNT => *DEFAULT_PAYMENT})
             .with_session_code(code, args)
             .with_address(admin_account_addr)
             .with_authorization_keys(&[admin_account_addr])
             .with_deploy_hash(rng.gen())
             .build();
-        execute_request = ExecuteRequestBuilder::from_deploy_item(deploy).build();
+        let execute_request = ExecuteRequestBuilder::from_deploy_item(deploy)
+            .with_block_time(0)
+            .build();
         builder.exec(execute_request).expect_success().commit();
 
-        Self {
+        let contract_hash = Self::query_contract_hash(&builder, admin_account_addr);
+        let mut vesting = Self {
             builder,
             admin_account: (admin_public_key, admin_account_addr),
             contract_hash,
+            condition_contract_hash,
             ali_account: (ali_public_key, ali_account_addr),
             bob_account: (bob_public_key, bob_account_addr),
             current_time: 0,
+        };
+        if config.fund_on_deploy && config.layout_0.is_none() {
+            vesting.deposit(admin_account_addr, config.total_amount);
         }
+        vesting
+    }
+
+    // Transfers `amount` from the sender's main purse through the deposit session.
+    pub fn deposit(&mut self, sender: AccountHash, amount: U512) -> U512 {
+        let args = runtime_args! {
+            arg::DEPOSIT_CONTRACT_HASH => self.contract_hash,
+            arg::AMOUNT => amount,
+        };
+        self.run_deposit(sender, args)
+    }
+
+    pub fn deposit_with_transfer_id(
+        &mut self,
+        sender: AccountHash,
+        amount: U512,
+        transfer_id: u64,
+    ) -> U512 {
+        let args = runtime_args! {
+            arg::DEPOSIT_CONTRACT_HASH => self.contract_hash,
+            arg::AMOUNT => amount,
+            arg::TRANSFER_ID => transfer_id,
+        };
+        self.run_deposit(sender, args)
+    }
+
+    fn run_deposit(&mut self, sender: AccountHash, args: RuntimeArgs) -> U512 {
+        let mut rng = rand::thread_rng();
+        let deploy = DeployItemBuilder::new()
+            .with_empty_payment_bytes(runtime_args! {ARG_AMOUNT => *DEFAULT_PAYMENT})
+            .with_session_code(PathBuf::from("deposit.wasm"), args)
+            .with_address(sender)
+            .with_authorization_keys(&[sender])
+            .with_deploy_hash(rng.gen())
+            .build();
+        let execute_request = ExecuteRequestBuilder::from_deploy_item(deploy)
+            .with_block_time(self.current_time)
+            .build();
+        self.builder.exec(execute_request).expect_success().commit();
+        self.builder.last_exec_gas_cost().value()
+    }
+
+    // Creates an account from `seed`, with enough motes to pay for its deploys.
+    pub fn create_funded_account(&mut self, seed: u8) -> AccountHash {
+        let mut rng = rand::thread_rng();
+        let public_key: PublicKey = (&SecretKey::ed25519_from_bytes([seed; 32]).unwrap()).into();
+        let deploy_item = DeployItemBuilder::new()
+            .with_address(*DEFAULT_ACCOUNT_ADDR)
+            .with_authorization_keys(&[*DEFAULT_ACCOUNT_ADDR])
+            .with_empty_payment_bytes(runtime_args! {ARG_AMOUNT => *DEFAULT_PAYMENT})
+            .with_transfer_args(runtime_args! {
+                mint::ARG_AMOUNT => U512::from(30_000_000_000_000u64),
+                mint::ARG_TARGET => public_key.clone(),
+                mint::ARG_ID => <Option::<u64>>::None
+            })
+            .with_deploy_hash(rng.gen())
+            .build();
+        let execute_request = ExecuteRequestBuilder::from_deploy_item(deploy_item).build();
+        self.builder.exec(execute_request).expect_success().commit();
+        AccountHash::from(&public_key)
+    }
+
+    fn query_contract_hash(
+        builder: &WasmTestBuilder<InMemoryGlobalState>,
+        installer: AccountHash,
+    ) -> ContractHash {
+        builder
+            .query(
+                None,
+                Key::Account(installer),
+                &["vesting_contract_hash".to_string()],
+            )
+            .expect("should be stored value.")
+            .as_cl_value()
+            .expect("should be cl value.")
+            .clone()
+            .into_t()
+            .expect("should be string.")
     }
 
     pub fn query_contract<T: CLTyped + FromBytes>(&self, name: &str) -> Option<T> {
@@ -220,51 +531,481 @@ impl Vesting {
         }
     }
 
+    pub fn get_state(&self) -> VestingState {
+        self.query_contract(key::STATE)
+            .expect("should have vesting state.")
+    }
+
+    pub fn get_grant_info(&self) -> GrantInfo {
+        self.query_contract(key::GRANT_INFO)
+            .expect("should have grant info.")
+    }
+
+    // Looks the grant up in the installing account's registry.
+    pub fn get_grant(&self, grant_id: &str) -> Option<ContractHash> {
+        let account = self
+            .builder
+            .get_account(self.admin_account.1)
+            .expect("should have account.");
+        let grants = account
+            .named_keys()
+            .get(key::GRANTS)
+            .and_then(Key::as_uref)?;
+        let value = self
+            .builder
+            .query(None, Key::dictionary(*grants, grant_id.as_bytes()), &[])
+            .ok()?;
+        let contract_hash = value
+            .as_cl_value()
+            .expect("should be cl value.")
+            .clone()
+            .into_t()
+            .expect("should be contract hash.");
+        Some(contract_hash)
+    }
+
+    // Amount recorded for `funder` in the contract's deposits dictionary.
+    pub fn get_deposited_amount(&self, funder: AccountHash) -> u64 {
+        let contract = self
+            .builder
+            .get_contract(self.contract_hash)
+            .expect("should have contract.");
+        let deposits = match contract.named_keys().get(key::DEPOSITS) {
+            Some(key) => *key.as_uref().expect("should be uref."),
+            None => return 0,
+        };
+        match self.builder.query(
+            None,
+            Key::dictionary(deposits, funder.to_string().as_bytes()),
+            &[],
+        ) {
+            Err(_) => 0,
+            Ok(value) => value
+                .as_cl_value()
+                .expect("should be cl value.")
+                .clone()
+                .into_t::<U512>()
+                .expect("should be U512.")
+                .as_u64(),
+        }
+    }
+
+    pub fn get_event_count(&self) -> u64 {
+        self.query_contract(key::EVENT_COUNT).unwrap_or_default()
+    }
+
+    pub fn get_event(&self, index: u64) -> VestingEvent {
+        self.query_dictionary(key::EVENTS, &index.to_string())
+            .expect("should have event.")
+    }
+
+    // Item `item_key` of the contract's dictionary `name`, if both exist.
+    pub fn query_dictionary<T: CLTyped + FromBytes>(
+        &self,
+        name: &str,
+        item_key: &str,
+    ) -> Option<T> {
+        let contract = self
+            .builder
+            .get_contract(self.contract_hash)
+            .expect("should have contract.");
+        let dictionary = *contract.named_keys().get(name).and_then(Key::as_uref)?;
+        let value = self
+            .builder
+            .query(None, Key::dictionary(dictionary, item_key.as_bytes()), &[])
+            .ok()?;
+        let item = value
+            .as_cl_value()
+            .expect("should be cl value.")
+            .clone()
+            .into_t()
+            .expect("should be dictionary item.");
+        Some(item)
+    }
+
+    // The first `count` items of a list the contract keeps in a dictionary by index.
+    fn get_list<T: CLTyped + FromBytes>(&self, name: &str, count: u32) -> Vec<T> {
+        (0..count)
+            .map(|index| {
+                self.query_dictionary(name, &index.to_string())
+                    .expect("should have list item.")
+            })
+            .collect()
+    }
+
+    pub fn get_funders(&self) -> Vec<AccountHash> {
+        self.get_list(key::FUNDERS, self.get_state().funder_count)
+    }
+
+    pub fn get_milestones(&self) -> Vec<Milestone> {
+        self.get_list(key::MILESTONES, self.get_state().milestone_count)
+    }
+
+    pub fn get_payout_splits(&self) -> Vec<PayoutSplit> {
+        self.get_list(key::PAYOUT_SPLITS, self.get_state().payout_split_count)
+    }
+
     pub fn get_total_amount(&self) -> u64 {
-        let balance: Option<U512> = self.query_contract(arg::TOTAL_AMOUNT);
-        balance.unwrap().as_u64()
+        self.get_state().total_amount.as_u64()
     }
 
     pub fn get_released_amount(&self) -> u64 {
-        let amount: Option<U512> = self.query_contract(arg::RELEASED_AMOUNT);
-        amount.unwrap().as_u64()
+        self.get_state().released_amount.as_u64()
     }
 
     pub fn get_pause_status(&self) -> bool {
-        let status: Option<bool> = self.query_contract(arg::PAUSE_FLAG);
-        status.unwrap()
+        self.get_state().is_paused
+    }
+
+    pub fn get_delegated_amount(&self) -> u64 {
+        self.get_state().delegated_amount.as_u64()
+    }
+
+    pub fn get_unbonding_amount(&self) -> u64 {
+        self.get_state().unbonding_amount.as_u64()
+    }
+
+    pub fn query_account<T: CLTyped + FromBytes>(&self, account: AccountHash, name: &str) -> T {
+        self.builder
+            .query(None, Key::Account(account), &[name.to_string()])
+            .expect("should be stored value.")
+            .as_cl_value()
+            .expect("should be cl value.")
+            .clone()
+            .into_t()
+            .expect("Wrong type in query result.")
+    }
+
+    pub fn get_purse_balance(&self) -> u64 {
+        let contract = self
+            .builder
+            .get_contract(self.contract_hash)
+            .expect("should have contract.");
+        let purse = contract
+            .named_keys()
+            .get(key::PURSE_NAME)
+            .and_then(Key::as_uref)
+            .expect("should have purse.");
+        self.builder.get_purse_balance(*purse).as_u64()
+    }
+
+    // The purse the deposit session keeps under the named keys of `account`, if it deposited.
+    pub fn get_deposit_purse(&self, account: AccountHash) -> Option<URef> {
+        let account = self
+            .builder
+            .get_account(account)
+            .expect("should have account.");
+        account
+            .named_keys()
+            .get(key::DEPOSIT_PURSE)
+            .and_then(Key::as_uref)
+            .copied()
+    }
+
+    pub fn get_account_balance(&self, account: AccountHash) -> u64 {
+        let account = self
+            .builder
+            .get_account(account)
+            .expect("should have account.");
+        self.builder
+            .get_purse_balance(account.main_purse())
+            .as_u64()
+    }
+
+    pub fn get_staked_amount(&self, validator: &PublicKey, delegator: &PublicKey) -> u64 {
+        let bids = self.builder.get_bids();
+        bids.get(validator)
+            .and_then(|bid| bid.delegators().get(delegator))
+            .map(|delegator| delegator.staked_amount().as_u64())
+            .unwrap_or_default()
+    }
+
+    // Ids of the transfers made by the last deploy, in the order they were made.
+    pub fn last_transfer_ids(&self) -> Vec<u64> {
+        let exec_results = self
+            .builder
+            .get_last_exec_results()
+            .expect("should have exec results.");
+        exec_results[0]
+            .transfers()
+            .iter()
+            .map(|transfer_addr| {
+                match self
+                    .builder
+                    .query(None, Key::Transfer(*transfer_addr), &[])
+                    .expect("should have transfer.")
+                {
+                    StoredValue::Transfer(transfer) => transfer.id.expect("should have id."),
+                    _ => panic!("should be transfer."),
+                }
+            })
+            .collect()
     }
 
     pub fn set_block_time(&mut self, block_time: u64) {
         self.current_time = block_time;
     }
 
-    pub fn withdraw(&mut self, sender: AccountHash, amount: u64) {
+    pub fn withdraw(&mut self, sender: AccountHash, amount: u64) -> U512 {
         self.call_indirect(
             sender,
             method::WITHDRAW,
             runtime_args! {
                 arg::AMOUNT => U512::from(amount)
             },
+        )
+    }
+
+    pub fn withdraw_with_transfer_id(
+        &mut self,
+        sender: AccountHash,
+        amount: u64,
+        transfer_id: u64,
+    ) -> U512 {
+        self.call_indirect(
+            sender,
+            method::WITHDRAW,
+            runtime_args! {
+                arg::AMOUNT => U512::from(amount),
+                arg::TRANSFER_ID => transfer_id,
+            },
+        )
+    }
+
+    pub fn pause(&mut self, sender: AccountHash) -> U512 {
+        self.call_indirect(sender, method::PAUSE, runtime_args! {})
+    }
+
+    pub fn unpause(&mut self, sender: AccountHash) -> U512 {
+        self.call_indirect(sender, method::UNPAUSE, runtime_args! {})
+    }
+
+    pub fn admin_release(&mut self, sender: AccountHash) -> U512 {
+        self.call_indirect(sender, method::ADMIN_RELEASE, runtime_args! {})
+    }
+
+    pub fn accelerate(&mut self, sender: AccountHash, amount: u64) -> U512 {
+        self.call_indirect(
+            sender,
+            method::ACCELERATE,
+            runtime_args! {
+                arg::AMOUNT => U512::from(amount)
+            },
+        )
+    }
+
+    pub fn accelerate_percentage(&mut self, sender: AccountHash, percentage: u8) -> U512 {
+        self.call_indirect(
+            sender,
+            method::ACCELERATE,
+            runtime_args! {
+                arg::PERCENTAGE => percentage
+            },
+        )
+    }
+
+    pub fn complete_milestone(&mut self, sender: AccountHash, id: u32) -> U512 {
+        self.call_indirect(
+            sender,
+            method::COMPLETE_MILESTONE,
+            runtime_args! {
+                arg::ID => id
+            },
+        )
+    }
+
+    // Adds `account` to the allowlist of the mock condition contract.
+    pub fn allow_withdrawals(&mut self, account: AccountHash) {
+        let condition_contract_hash = self
+            .condition_contract_hash
+            .expect("should have condition contract.");
+        self.call_stored(
+            self.admin_account.1,
+            condition_contract_hash,
+            method::ALLOW,
+            runtime_args! {
+                arg::ACCOUNT => account
+            },
         );
     }
 
-    pub fn pause(&mut self, sender: AccountHash) {
-        self.call_indirect(sender, method::PAUSE, runtime_args! {});
+    pub fn propose_release_destination(&mut self, sender: AccountHash, destination: Key) -> U512 {
+        self.call_indirect(
+            sender,
+            method::PROPOSE_RELEASE_DESTINATION,
+            runtime_args! {
+                arg::RELEASE_DESTINATION => destination
+            },
+        )
+    }
+
+    pub fn confirm_release_destination(&mut self, sender: AccountHash, destination: Key) -> U512 {
+        self.call_indirect(
+            sender,
+            method::CONFIRM_RELEASE_DESTINATION,
+            runtime_args! {
+                arg::RELEASE_DESTINATION => destination
+            },
+        )
+    }
+
+    pub fn admin_release_amount(&mut self, sender: AccountHash, amount: u64) -> U512 {
+        self.call_indirect(
+            sender,
+            method::ADMIN_RELEASE,
+            runtime_args! {
+                arg::AMOUNT => U512::from(amount)
+            },
+        )
+    }
+
+    pub fn sweep_expired(&mut self, sender: AccountHash) -> U512 {
+        self.call_indirect(sender, method::SWEEP_EXPIRED, runtime_args! {})
+    }
+
+    pub fn reclaim_forfeited(&mut self, sender: AccountHash) -> U512 {
+        self.call_indirect(sender, method::RECLAIM_FORFEITED, runtime_args! {})
     }
 
-    pub fn unpause(&mut self, sender: AccountHash) {
-        self.call_indirect(sender, method::UNPAUSE, runtime_args! {});
+    pub fn accept_grant(&mut self, sender: AccountHash, agreement_hash: Option<[u8; 32]>) -> U512 {
+        let mut args = runtime_args! {};
+        if let Some(agreement_hash) = agreement_hash {
+            args.insert(arg::AGREEMENT_HASH, agreement_hash).unwrap();
+        }
+        self.call_indirect(sender, method::ACCEPT_GRANT, args)
+    }
+
+    pub fn set_payout_splits(
+        &mut self,
+        sender: AccountHash,
+        payout_splits: Vec<(AccountHash, u16)>,
+    ) -> U512 {
+        let args = runtime_args! {
+            arg::PAYOUT_SPLITS => payout_splits,
+        };
+        self.call_indirect(sender, method::SET_PAYOUT_SPLITS, args)
+    }
+
+    pub fn cancel_grant(&mut self, sender: AccountHash) -> U512 {
+        self.call_indirect(sender, method::CANCEL_GRANT, runtime_args! {})
+    }
+
+    pub fn close(&mut self, sender: AccountHash) -> U512 {
+        self.call_indirect(sender, method::CLOSE, runtime_args! {})
+    }
+
+    pub fn sweep_excess(&mut self, sender: AccountHash) -> U512 {
+        self.call_indirect(sender, method::SWEEP_EXCESS, runtime_args! {})
     }
 
-    pub fn admin_release(&mut self, sender: AccountHash) {
-        self.call_indirect(sender, method::ADMIN_RELEASE, runtime_args! {});
+    pub fn add_bid(&mut self, sender: AccountHash, public_key: PublicKey, amount: u64) {
+        let auction = self.builder.get_auction_contract_hash();
+        let execute_request = ExecuteRequestBuilder::contract_call_by_hash(
+            sender,
+            auction,
+            auction::METHOD_ADD_BID,
+            runtime_args! {
+                auction::ARG_PUBLIC_KEY => public_key,
+                auction::ARG_AMOUNT => U512::from(amount),
+                auction::ARG_DELEGATION_RATE => 0u8,
+            },
+        )
+        .build();
+        self.builder.exec(execute_request).expect_success().commit();
     }
 
-    fn call_indirect(&mut self, sender: AccountHash, method: &str, args: RuntimeArgs) {
+    pub fn delegate(
+        &mut self,
+        sender: AccountHash,
+        delegator: PublicKey,
+        validator: PublicKey,
+        amount: u64,
+    ) -> U512 {
+        self.call_indirect(
+            sender,
+            method::DELEGATE,
+            runtime_args! {
+                auction::ARG_DELEGATOR => delegator,
+                auction::ARG_VALIDATOR => validator,
+                auction::ARG_AMOUNT => U512::from(amount),
+            },
+        )
+    }
+
+    pub fn undelegate(
+        &mut self,
+        sender: AccountHash,
+        delegator: PublicKey,
+        validator: PublicKey,
+        amount: u64,
+    ) -> U512 {
+        self.call_indirect(
+            sender,
+            method::UNDELEGATE,
+            runtime_args! {
+                auction::ARG_DELEGATOR => delegator,
+                auction::ARG_VALIDATOR => validator,
+                auction::ARG_AMOUNT => U512::from(amount),
+            },
+        )
+    }
+
+    pub fn return_stake(&mut self, sender: AccountHash, amount: u64) -> U512 {
+        self.call_session(
+            sender,
+            "return_stake.wasm",
+            runtime_args! {
+                arg::VESTING_CONTRACT_HASH => self.contract_hash,
+                arg::AMOUNT => U512::from(amount),
+            },
+        )
+    }
+
+    // Settles `amount` already in the vesting purse, see `return_stake` for the whole session.
+    pub fn settle_unbonded(&mut self, sender: AccountHash, amount: u64) -> U512 {
+        self.call_indirect(
+            sender,
+            method::SETTLE_UNBONDED,
+            runtime_args! {
+                arg::AMOUNT => U512::from(amount)
+            },
+        )
+    }
+
+    pub fn call_proxy(&mut self, sender: AccountHash, method: &str, mut args: RuntimeArgs) -> U512 {
+        args.insert(arg::VESTING_CONTRACT_HASH, self.contract_hash)
+            .unwrap();
+        args.insert(arg::METHOD, method.to_string()).unwrap();
+        self.call_session(sender, "vesting_proxy.wasm", args)
+    }
+
+    pub fn migrate(&mut self, sender: AccountHash) -> U512 {
+        self.call_indirect(sender, method::MIGRATE, runtime_args! {})
+    }
+
+    pub fn upgrade(&mut self, sender: AccountHash) -> U512 {
+        let cost = self.call_session(
+            sender,
+            "contract.wasm",
+            runtime_args! {
+                arg::UPGRADE => true
+            },
+        );
+        self.contract_hash = Self::query_contract_hash(&self.builder, sender);
+        cost
+    }
+
+    pub fn advance_eras(&mut self, eras: u64) {
+        for _ in 0..eras {
+            self.builder.run_auction(self.current_time, Vec::new());
+        }
+    }
+
+    // Runs `wasm` as session code and returns its gas cost.
+    fn call_session(&mut self, sender: AccountHash, wasm: &str, args: RuntimeArgs) -> U512 {
         let mut rng = rand::thread_rng();
         let deploy = DeployItemBuilder::new()
-            .with_stored_session_hash(self.contract_hash, method, args)
+            .with_session_code(PathBuf::from(wasm), args)
             .with_empty_payment_bytes(runtime_args! {ARG_AMOUNT => *DEFAULT_PAYMENT})
             .with_address(sender)
             .with_authorization_keys(&[sender])
@@ -274,5 +1015,57 @@ impl Vesting {
             .with_block_time(self.current_time)
             .build();
         self.builder.exec(execute_request).expect_success().commit();
+        self.builder.last_exec_gas_cost().value()
+    }
+
+    fn call_indirect(&mut self, sender: AccountHash, method: &str, args: RuntimeArgs) -> U512 {
+        self.call_stored(sender, self.contract_hash, method, args)
+    }
+
+    // Calls `method` on the contract expecting it to revert, and returns the error it reverts with.
+    pub fn call_reverting(
+        &mut self,
+        sender: AccountHash,
+        method: &str,
+        args: RuntimeArgs,
+    ) -> ApiError {
+        let execute_request = self.stored_call_request(sender, self.contract_hash, method, args);
+        self.builder.exec(execute_request).expect_failure().commit();
+        match self.builder.get_error() {
+            Some(engine_state::Error::Exec(execution::Error::Revert(error))) => error,
+            error => panic!("should have reverted, got {:?}", error),
+        }
+    }
+
+    fn call_stored(
+        &mut self,
+        sender: AccountHash,
+        contract_hash: ContractHash,
+        method: &str,
+        args: RuntimeArgs,
+    ) -> U512 {
+        let execute_request = self.stored_call_request(sender, contract_hash, method, args);
+        self.builder.exec(execute_request).expect_success().commit();
+        self.builder.last_exec_gas_cost().value()
+    }
+
+    fn stored_call_request(
+        &self,
+        sender: AccountHash,
+        contract_hash: ContractHash,
+        method: &str,
+        args: RuntimeArgs,
+    ) -> engine_state::ExecuteRequest {
+        let mut rng = rand::thread_rng();
+        let deploy = DeployItemBuilder::new()
+            .with_stored_session_hash(contract_hash, method, args)
+            .with_empty_payment_bytes(runtime_args! {ARG_AMOUNT => *DEFAULT_PAYMENT})
+            .with_address(sender)
+            .with_authorization_keys(&[sender])
+            .with_deploy_hash(rng.gen())
+            .build();
+        ExecuteRequestBuilder::from_deploy_item(deploy)
+            .with_block_time(self.current_time)
+            .build()
     }
 }
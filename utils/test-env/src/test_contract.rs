@@ -1,7 +1,7 @@
 use std::path::PathBuf;
 
 use casper_types::{
-    account::AccountHash, bytesrepr::FromBytes, CLTyped, ContractHash, RuntimeArgs,
+    account::AccountHash, bytesrepr::FromBytes, CLTyped, ContractHash, RuntimeArgs, U512,
 };
 
 use crate::{utils::DeploySource, TestEnv};
@@ -52,11 +52,16 @@ impl TestContract {
             .query_account_named_key(self.contract_owner, &[key])
     }
 
-    pub fn call_contract(&self, sender: AccountHash, entry_point: &str, session_args: RuntimeArgs) {
+    pub fn call_contract(
+        &self,
+        sender: AccountHash,
+        entry_point: &str,
+        session_args: RuntimeArgs,
+    ) -> U512 {
         let session_code = DeploySource::ByHash {
             hash: ContractHash::new(self.contract_hash()),
             method: entry_point.to_string(),
         };
-        self.env.run(sender, session_code, session_args);
+        self.env.run(sender, session_code, session_args)
     }
 }
@@ -3,6 +3,7 @@ use std::sync::{Arc, Mutex};
 use casper_engine_test_support::{InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST};
 use casper_types::{
     account::AccountHash, bytesrepr::FromBytes, CLTyped, Key, PublicKey, RuntimeArgs, SecretKey,
+    U512,
 };
 
 use crate::utils::{deploy, fund_account, query, query_dictionary_item, DeploySource};
@@ -19,7 +20,12 @@ impl TestEnv {
         }
     }
 
-    pub fn run(&self, sender: AccountHash, session_code: DeploySource, session_args: RuntimeArgs) {
+    pub fn run(
+        &self,
+        sender: AccountHash,
+        session_code: DeploySource,
+        session_args: RuntimeArgs,
+    ) -> U512 {
         deploy(
             &mut self.state.lock().unwrap().builder,
             &sender,
@@ -116,7 +122,7 @@ impl TestEnvState {
         sender: AccountHash,
         session_code: DeploySource,
         session_args: RuntimeArgs,
-    ) {
+    ) -> U512 {
         deploy(
             &mut self.builder,
             &sender,
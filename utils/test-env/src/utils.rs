@@ -53,7 +53,7 @@ pub fn deploy(
     args: RuntimeArgs,
     success: bool,
     block_time: Option<u64>,
-) {
+) -> U512 {
     let mut deploy_builder = DeployItemBuilder::new()
         .with_empty_payment_bytes(runtime_args! {ARG_AMOUNT => *DEFAULT_PAYMENT})
         .with_address(*deployer)
@@ -78,6 +78,7 @@ pub fn deploy(
         exec.expect_failure()
     }
     .commit();
+    builder.last_exec_gas_cost().value()
 }
 
 pub fn query_dictionary_item(
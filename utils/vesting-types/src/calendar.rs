@@ -0,0 +1,82 @@
+//! UTC calendar arithmetic on block time timestamps, in milliseconds since the Unix epoch.
+//! Dates follow the proleptic Gregorian calendar.
+
+use core::cmp;
+
+use crate::Time;
+
+pub const MS_PER_DAY: Time = 86_400_000;
+
+pub fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+/// Number of days in `month` (1 to 12) of `year`.
+pub fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        _ => 0,
+    }
+}
+
+/// Number of days from 1970-01-01 to the given date.
+pub fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = (if year >= 0 { year } else { year - 399 }) / 400;
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Date `(year, month, day)` that is `days` days after 1970-01-01.
+pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = (if days >= 0 { days } else { days - 146_096 }) / 146_097;
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+    let month = (if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    }) as u32;
+    let year = year_of_era + era * 400;
+    (if month <= 2 { year + 1 } else { year }, month, day)
+}
+
+/// Moves `timestamp` forward by `months` calendar months, keeping its time of day and
+/// its day of month. Days past the end of a shorter month fall on its last day,
+/// so a schedule anchored on the 31st unlocks on February 28th or 29th.
+pub fn add_months(timestamp: Time, months: u64) -> Time {
+    let (year, month, day) = civil_from_days((timestamp / MS_PER_DAY) as i64);
+    let month_index = year * 12 + month as i64 - 1 + months as i64;
+    let target_year = month_index.div_euclid(12);
+    let target_month = month_index.rem_euclid(12) as u32 + 1;
+    let target_day = cmp::min(day, days_in_month(target_year, target_month));
+    days_from_civil(target_year, target_month, target_day) as Time * MS_PER_DAY
+        + timestamp % MS_PER_DAY
+}
+
+/// Number of whole calendar months, as counted by `add_months`, from `start` to `now`.
+pub fn months_between(start: Time, now: Time) -> u64 {
+    if now <= start {
+        return 0;
+    }
+    let (start_year, start_month, _) = civil_from_days((start / MS_PER_DAY) as i64);
+    let (now_year, now_month, _) = civil_from_days((now / MS_PER_DAY) as i64);
+    let months = ((now_year - start_year) * 12 + now_month as i64 - start_month as i64) as u64;
+    if months > 0 && add_months(start, months) > now {
+        months - 1
+    } else {
+        months
+    }
+}
@@ -0,0 +1,18 @@
+u8_enum! {
+    /// What the cliff, drip and pause times of a contract are measured in.
+    ///
+    /// Block height isn't supported, as the host doesn't expose it to contracts. Its value, `1`,
+    /// is left unused.
+    pub enum ClockSource {
+        /// Block time, in milliseconds.
+        BlockTime = 0,
+        /// Id of the current era.
+        EraId = 2,
+    }
+}
+
+impl Default for ClockSource {
+    fn default() -> Self {
+        ClockSource::BlockTime
+    }
+}
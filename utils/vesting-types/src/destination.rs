@@ -0,0 +1,67 @@
+use alloc::vec::Vec;
+
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
+    CLType, CLTyped, URef,
+};
+
+const ACCOUNT_TAG: u8 = 0;
+const PURSE_TAG: u8 = 1;
+
+/// Where the funds taken back by the admin are sent.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReleaseDestination {
+    /// Main purse of an account.
+    Account(AccountHash),
+    /// A purse with add access rights.
+    Purse(URef),
+}
+
+impl CLTyped for ReleaseDestination {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl ToBytes for ReleaseDestination {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        match self {
+            ReleaseDestination::Account(account_hash) => {
+                result.push(ACCOUNT_TAG);
+                result.append(&mut account_hash.to_bytes()?);
+            }
+            ReleaseDestination::Purse(purse) => {
+                result.push(PURSE_TAG);
+                result.append(&mut purse.to_bytes()?);
+            }
+        }
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+            + match self {
+                ReleaseDestination::Account(account_hash) => account_hash.serialized_length(),
+                ReleaseDestination::Purse(purse) => purse.serialized_length(),
+            }
+    }
+}
+
+impl FromBytes for ReleaseDestination {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, bytes) = u8::from_bytes(bytes)?;
+        match tag {
+            ACCOUNT_TAG => {
+                let (account_hash, bytes) = AccountHash::from_bytes(bytes)?;
+                Ok((ReleaseDestination::Account(account_hash), bytes))
+            }
+            PURSE_TAG => {
+                let (purse, bytes) = URef::from_bytes(bytes)?;
+                Ok((ReleaseDestination::Purse(purse), bytes))
+            }
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
@@ -0,0 +1,502 @@
+use alloc::vec::Vec;
+
+use crate::{Amount, PayoutSplit, ReleaseDestination, Time};
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
+    CLType, CLTyped,
+};
+
+const ACCELERATED_TAG: u8 = 0;
+const MILESTONE_COMPLETED_TAG: u8 = 1;
+const MILESTONE_RECLAIMED_TAG: u8 = 2;
+const RELEASE_DESTINATION_PROPOSED_TAG: u8 = 3;
+const RELEASE_DESTINATION_CHANGED_TAG: u8 = 4;
+const RECLAIMED_TAG: u8 = 5;
+const SWEPT_TAG: u8 = 6;
+const FORFEITED_TAG: u8 = 7;
+const GRANT_ACCEPTED_TAG: u8 = 8;
+const GRANT_CANCELLED_TAG: u8 = 9;
+const CLOSED_TAG: u8 = 10;
+const EXCESS_SWEPT_TAG: u8 = 11;
+const REFUNDED_TAG: u8 = 12;
+const PAYOUT_SPLITS_CHANGED_TAG: u8 = 13;
+const FEE_CHARGED_TAG: u8 = 14;
+
+/// Audit record of an admin action, stored in the contract's `events` dictionary
+/// under its index, starting from `"0"`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VestingEvent {
+    /// `amount` of the unvested balance was made available to the recipient at `timestamp`.
+    Accelerated { amount: Amount, timestamp: Time },
+    /// Milestone `id` was attested as completed at `timestamp`.
+    MilestoneCompleted { id: u32, timestamp: Time },
+    /// The `amount` of expired milestone `id` went back to the admin at `timestamp`.
+    MilestoneReclaimed {
+        id: u32,
+        amount: Amount,
+        timestamp: Time,
+    },
+    /// The admin proposed a new release destination at `timestamp`.
+    ReleaseDestinationProposed {
+        destination: ReleaseDestination,
+        timestamp: Time,
+    },
+    /// The admin confirmed the proposed release destination at `timestamp`.
+    ReleaseDestinationChanged {
+        destination: ReleaseDestination,
+        timestamp: Time,
+    },
+    /// `amount` of the unvested balance went back to the admin at `timestamp`.
+    Reclaimed { amount: Amount, timestamp: Time },
+    /// `amount` left unclaimed after the claim deadline went back to the admin at `timestamp`.
+    Swept { amount: Amount, timestamp: Time },
+    /// `amount` the schedule would have vested during the pause ended at `timestamp` was forfeited.
+    Forfeited { amount: Amount, timestamp: Time },
+    /// The recipient accepted the grant at `timestamp`, along with the agreement hash if passed.
+    GrantAccepted {
+        agreement_hash: Option<[u8; 32]>,
+        timestamp: Time,
+    },
+    /// The admin cancelled the grant before acceptance and took back `amount` at `timestamp`.
+    GrantCancelled { amount: Amount, timestamp: Time },
+    /// The admin closed the finished grant at `timestamp`, sweeping `dust` left in the purse.
+    Closed { dust: Amount, timestamp: Time },
+    /// `amount` deposited on top of the grant was sent back at `timestamp`.
+    ExcessSwept { amount: Amount, timestamp: Time },
+    /// `amount` taken back from the grant was refunded to `funder` at `timestamp`,
+    /// by the transfer with `transfer_id`.
+    Refunded {
+        funder: AccountHash,
+        amount: Amount,
+        transfer_id: u64,
+        timestamp: Time,
+    },
+    /// The recipient replaced the payout splits at `timestamp`.
+    PayoutSplitsChanged {
+        payout_splits: Vec<PayoutSplit>,
+        timestamp: Time,
+    },
+    /// A fee of `amount` went to the fee collector at `timestamp` by the transfer with
+    /// `transfer_id`, bringing the fees paid to `fees_paid`.
+    FeeCharged {
+        amount: Amount,
+        fees_paid: Amount,
+        transfer_id: u64,
+        timestamp: Time,
+    },
+}
+
+impl CLTyped for VestingEvent {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl ToBytes for VestingEvent {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        match self {
+            VestingEvent::Accelerated { amount, timestamp } => {
+                result.push(ACCELERATED_TAG);
+                result.append(&mut amount.to_bytes()?);
+                result.append(&mut timestamp.to_bytes()?);
+            }
+            VestingEvent::MilestoneCompleted { id, timestamp } => {
+                result.push(MILESTONE_COMPLETED_TAG);
+                result.append(&mut id.to_bytes()?);
+                result.append(&mut timestamp.to_bytes()?);
+            }
+            VestingEvent::MilestoneReclaimed {
+                id,
+                amount,
+                timestamp,
+            } => {
+                result.push(MILESTONE_RECLAIMED_TAG);
+                result.append(&mut id.to_bytes()?);
+                result.append(&mut amount.to_bytes()?);
+                result.append(&mut timestamp.to_bytes()?);
+            }
+            VestingEvent::ReleaseDestinationProposed {
+                destination,
+                timestamp,
+            } => {
+                result.push(RELEASE_DESTINATION_PROPOSED_TAG);
+                result.append(&mut destination.to_bytes()?);
+                result.append(&mut timestamp.to_bytes()?);
+            }
+            VestingEvent::ReleaseDestinationChanged {
+                destination,
+                timestamp,
+            } => {
+                result.push(RELEASE_DESTINATION_CHANGED_TAG);
+                result.append(&mut destination.to_bytes()?);
+                result.append(&mut timestamp.to_bytes()?);
+            }
+            VestingEvent::Reclaimed { amount, timestamp } => {
+                result.push(RECLAIMED_TAG);
+                result.append(&mut amount.to_bytes()?);
+                result.append(&mut timestamp.to_bytes()?);
+            }
+            VestingEvent::Swept { amount, timestamp } => {
+                result.push(SWEPT_TAG);
+                result.append(&mut amount.to_bytes()?);
+                result.append(&mut timestamp.to_bytes()?);
+            }
+            VestingEvent::Forfeited { amount, timestamp } => {
+                result.push(FORFEITED_TAG);
+                result.append(&mut amount.to_bytes()?);
+                result.append(&mut timestamp.to_bytes()?);
+            }
+            VestingEvent::GrantAccepted {
+                agreement_hash,
+                timestamp,
+            } => {
+                result.push(GRANT_ACCEPTED_TAG);
+                result.append(&mut agreement_hash.to_bytes()?);
+                result.append(&mut timestamp.to_bytes()?);
+            }
+            VestingEvent::GrantCancelled { amount, timestamp } => {
+                result.push(GRANT_CANCELLED_TAG);
+                result.append(&mut amount.to_bytes()?);
+                result.append(&mut timestamp.to_bytes()?);
+            }
+            VestingEvent::Closed { dust, timestamp } => {
+                result.push(CLOSED_TAG);
+                result.append(&mut dust.to_bytes()?);
+                result.append(&mut timestamp.to_bytes()?);
+            }
+            VestingEvent::ExcessSwept { amount, timestamp } => {
+                result.push(EXCESS_SWEPT_TAG);
+                result.append(&mut amount.to_bytes()?);
+                result.append(&mut timestamp.to_bytes()?);
+            }
+            VestingEvent::Refunded {
+                funder,
+                amount,
+                transfer_id,
+                timestamp,
+            } => {
+                result.push(REFUNDED_TAG);
+                result.append(&mut funder.to_bytes()?);
+                result.append(&mut amount.to_bytes()?);
+                result.append(&mut transfer_id.to_bytes()?);
+                result.append(&mut timestamp.to_bytes()?);
+            }
+            VestingEvent::PayoutSplitsChanged {
+                payout_splits,
+                timestamp,
+            } => {
+                result.push(PAYOUT_SPLITS_CHANGED_TAG);
+                result.append(&mut payout_splits.to_bytes()?);
+                result.append(&mut timestamp.to_bytes()?);
+            }
+            VestingEvent::FeeCharged {
+                amount,
+                fees_paid,
+                transfer_id,
+                timestamp,
+            } => {
+                result.push(FEE_CHARGED_TAG);
+                result.append(&mut amount.to_bytes()?);
+                result.append(&mut fees_paid.to_bytes()?);
+                result.append(&mut transfer_id.to_bytes()?);
+                result.append(&mut timestamp.to_bytes()?);
+            }
+        }
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+            + match self {
+                VestingEvent::Accelerated { amount, timestamp }
+                | VestingEvent::Reclaimed { amount, timestamp }
+                | VestingEvent::Swept { amount, timestamp }
+                | VestingEvent::Forfeited { amount, timestamp }
+                | VestingEvent::GrantCancelled { amount, timestamp }
+                | VestingEvent::ExcessSwept { amount, timestamp }
+                | VestingEvent::Closed {
+                    dust: amount,
+                    timestamp,
+                } => amount.serialized_length() + timestamp.serialized_length(),
+                VestingEvent::GrantAccepted {
+                    agreement_hash,
+                    timestamp,
+                } => agreement_hash.serialized_length() + timestamp.serialized_length(),
+                VestingEvent::PayoutSplitsChanged {
+                    payout_splits,
+                    timestamp,
+                } => payout_splits.serialized_length() + timestamp.serialized_length(),
+                VestingEvent::MilestoneCompleted { id, timestamp } => {
+                    id.serialized_length() + timestamp.serialized_length()
+                }
+                VestingEvent::MilestoneReclaimed {
+                    id,
+                    amount,
+                    timestamp,
+                } => {
+                    id.serialized_length()
+                        + amount.serialized_length()
+                        + timestamp.serialized_length()
+                }
+                VestingEvent::FeeCharged {
+                    amount,
+                    fees_paid,
+                    transfer_id,
+                    timestamp,
+                } => {
+                    amount.serialized_length()
+                        + fees_paid.serialized_length()
+                        + transfer_id.serialized_length()
+                        + timestamp.serialized_length()
+                }
+                VestingEvent::Refunded {
+                    funder,
+                    amount,
+                    transfer_id,
+                    timestamp,
+                } => {
+                    funder.serialized_length()
+                        + amount.serialized_length()
+                        + transfer_id.serialized_length()
+                        + timestamp.serialized_length()
+                }
+                VestingEvent::ReleaseDestinationProposed {
+                    destination,
+                    timestamp,
+                }
+                | VestingEvent::ReleaseDestinationChanged {
+                    destination,
+                    timestamp,
+                } => destination.serialized_length() + timestamp.serialized_length(),
+            }
+    }
+}
+
+impl FromBytes for VestingEvent {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, bytes) = u8::from_bytes(bytes)?;
+        match tag {
+            ACCELERATED_TAG => {
+                let (amount, bytes) = Amount::from_bytes(bytes)?;
+                let (timestamp, bytes) = Time::from_bytes(bytes)?;
+                Ok((VestingEvent::Accelerated { amount, timestamp }, bytes))
+            }
+            MILESTONE_COMPLETED_TAG => {
+                let (id, bytes) = u32::from_bytes(bytes)?;
+                let (timestamp, bytes) = Time::from_bytes(bytes)?;
+                Ok((VestingEvent::MilestoneCompleted { id, timestamp }, bytes))
+            }
+            MILESTONE_RECLAIMED_TAG => {
+                let (id, bytes) = u32::from_bytes(bytes)?;
+                let (amount, bytes) = Amount::from_bytes(bytes)?;
+                let (timestamp, bytes) = Time::from_bytes(bytes)?;
+                let event = VestingEvent::MilestoneReclaimed {
+                    id,
+                    amount,
+                    timestamp,
+                };
+                Ok((event, bytes))
+            }
+            RELEASE_DESTINATION_PROPOSED_TAG => {
+                let (destination, bytes) = ReleaseDestination::from_bytes(bytes)?;
+                let (timestamp, bytes) = Time::from_bytes(bytes)?;
+                let event = VestingEvent::ReleaseDestinationProposed {
+                    destination,
+                    timestamp,
+                };
+                Ok((event, bytes))
+            }
+            RELEASE_DESTINATION_CHANGED_TAG => {
+                let (destination, bytes) = ReleaseDestination::from_bytes(bytes)?;
+                let (timestamp, bytes) = Time::from_bytes(bytes)?;
+                let event = VestingEvent::ReleaseDestinationChanged {
+                    destination,
+                    timestamp,
+                };
+                Ok((event, bytes))
+            }
+            RECLAIMED_TAG => {
+                let (amount, bytes) = Amount::from_bytes(bytes)?;
+                let (timestamp, bytes) = Time::from_bytes(bytes)?;
+                Ok((VestingEvent::Reclaimed { amount, timestamp }, bytes))
+            }
+            SWEPT_TAG => {
+                let (amount, bytes) = Amount::from_bytes(bytes)?;
+                let (timestamp, bytes) = Time::from_bytes(bytes)?;
+                Ok((VestingEvent::Swept { amount, timestamp }, bytes))
+            }
+            FORFEITED_TAG => {
+                let (amount, bytes) = Amount::from_bytes(bytes)?;
+                let (timestamp, bytes) = Time::from_bytes(bytes)?;
+                Ok((VestingEvent::Forfeited { amount, timestamp }, bytes))
+            }
+            GRANT_ACCEPTED_TAG => {
+                let (agreement_hash, bytes) = Option::<[u8; 32]>::from_bytes(bytes)?;
+                let (timestamp, bytes) = Time::from_bytes(bytes)?;
+                let event = VestingEvent::GrantAccepted {
+                    agreement_hash,
+                    timestamp,
+                };
+                Ok((event, bytes))
+            }
+            GRANT_CANCELLED_TAG => {
+                let (amount, bytes) = Amount::from_bytes(bytes)?;
+                let (timestamp, bytes) = Time::from_bytes(bytes)?;
+                Ok((VestingEvent::GrantCancelled { amount, timestamp }, bytes))
+            }
+            CLOSED_TAG => {
+                let (dust, bytes) = Amount::from_bytes(bytes)?;
+                let (timestamp, bytes) = Time::from_bytes(bytes)?;
+                Ok((VestingEvent::Closed { dust, timestamp }, bytes))
+            }
+            EXCESS_SWEPT_TAG => {
+                let (amount, bytes) = Amount::from_bytes(bytes)?;
+                let (timestamp, bytes) = Time::from_bytes(bytes)?;
+                Ok((VestingEvent::ExcessSwept { amount, timestamp }, bytes))
+            }
+            REFUNDED_TAG => {
+                let (funder, bytes) = AccountHash::from_bytes(bytes)?;
+                let (amount, bytes) = Amount::from_bytes(bytes)?;
+                let (transfer_id, bytes) = u64::from_bytes(bytes)?;
+                let (timestamp, bytes) = Time::from_bytes(bytes)?;
+                let event = VestingEvent::Refunded {
+                    funder,
+                    amount,
+                    transfer_id,
+                    timestamp,
+                };
+                Ok((event, bytes))
+            }
+            FEE_CHARGED_TAG => {
+                let (amount, bytes) = Amount::from_bytes(bytes)?;
+                let (fees_paid, bytes) = Amount::from_bytes(bytes)?;
+                let (transfer_id, bytes) = u64::from_bytes(bytes)?;
+                let (timestamp, bytes) = Time::from_bytes(bytes)?;
+                let event = VestingEvent::FeeCharged {
+                    amount,
+                    fees_paid,
+                    transfer_id,
+                    timestamp,
+                };
+                Ok((event, bytes))
+            }
+            PAYOUT_SPLITS_CHANGED_TAG => {
+                let (payout_splits, bytes) = Vec::<PayoutSplit>::from_bytes(bytes)?;
+                let (timestamp, bytes) = Time::from_bytes(bytes)?;
+                let event = VestingEvent::PayoutSplitsChanged {
+                    payout_splits,
+                    timestamp,
+                };
+                Ok((event, bytes))
+            }
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use casper_types::{AccessRights, URef};
+
+    use super::*;
+
+    #[test]
+    fn events_bytes_round_trip() {
+        let events = vec![
+            VestingEvent::Accelerated {
+                amount: 10.into(),
+                timestamp: 1,
+            },
+            VestingEvent::MilestoneCompleted {
+                id: 2,
+                timestamp: 2,
+            },
+            VestingEvent::MilestoneReclaimed {
+                id: 3,
+                amount: 30.into(),
+                timestamp: 3,
+            },
+            VestingEvent::ReleaseDestinationProposed {
+                destination: ReleaseDestination::Account(AccountHash::new([1u8; 32])),
+                timestamp: 4,
+            },
+            VestingEvent::ReleaseDestinationChanged {
+                destination: ReleaseDestination::Purse(URef::new([2u8; 32], AccessRights::ADD)),
+                timestamp: 5,
+            },
+            VestingEvent::Reclaimed {
+                amount: 60.into(),
+                timestamp: 6,
+            },
+            VestingEvent::Swept {
+                amount: 70.into(),
+                timestamp: 7,
+            },
+            VestingEvent::Forfeited {
+                amount: 80.into(),
+                timestamp: 8,
+            },
+            VestingEvent::GrantAccepted {
+                agreement_hash: Some([3u8; 32]),
+                timestamp: 9,
+            },
+            VestingEvent::GrantAccepted {
+                agreement_hash: None,
+                timestamp: 10,
+            },
+            VestingEvent::GrantCancelled {
+                amount: 110.into(),
+                timestamp: 11,
+            },
+            VestingEvent::Closed {
+                dust: 1.into(),
+                timestamp: 12,
+            },
+            VestingEvent::ExcessSwept {
+                amount: 130.into(),
+                timestamp: 13,
+            },
+            VestingEvent::Refunded {
+                funder: AccountHash::new([4u8; 32]),
+                amount: 140.into(),
+                transfer_id: 14,
+                timestamp: 14,
+            },
+            VestingEvent::PayoutSplitsChanged {
+                payout_splits: vec![PayoutSplit {
+                    target: AccountHash::new([5u8; 32]),
+                    basis_points: 1_500,
+                }],
+                timestamp: 15,
+            },
+            VestingEvent::FeeCharged {
+                amount: 16.into(),
+                fees_paid: 160.into(),
+                transfer_id: 16,
+                timestamp: 16,
+            },
+        ];
+        for event in events {
+            let bytes = event.to_bytes().unwrap();
+            assert_eq!(bytes.len(), event.serialized_length());
+            let (decoded, rest) = VestingEvent::from_bytes(&bytes).unwrap();
+            assert_eq!(decoded, event);
+            assert!(rest.is_empty());
+        }
+    }
+
+    #[test]
+    fn event_with_unknown_tag_rejected() {
+        let mut bytes = VestingEvent::Closed {
+            dust: 1.into(),
+            timestamp: 1,
+        }
+        .to_bytes()
+        .unwrap();
+        bytes[0] = FEE_CHARGED_TAG + 1;
+        assert!(VestingEvent::from_bytes(&bytes).is_err());
+    }
+}
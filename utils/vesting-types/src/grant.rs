@@ -0,0 +1,59 @@
+use alloc::{boxed::Box, collections::BTreeMap, string::String, vec::Vec};
+
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLType, CLTyped,
+};
+
+/// What ties a vesting contract to its off-chain agreement and records.
+/// Set at install and never changed afterwards.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GrantInfo {
+    /// Hash of the terms document the grant was issued under.
+    pub terms_hash: Option<[u8; 32]>,
+    /// Identifier of the grant in the issuer's records, unique among the installer's grants.
+    pub grant_id: Option<String>,
+    /// Free-form details, such as a title or a department.
+    pub metadata: BTreeMap<String, String>,
+}
+
+// Serialized like the tuple of its fields, so clients without this crate can decode it as one.
+impl CLTyped for GrantInfo {
+    fn cl_type() -> CLType {
+        CLType::Tuple3([
+            Box::new(Option::<[u8; 32]>::cl_type()),
+            Box::new(Option::<String>::cl_type()),
+            Box::new(BTreeMap::<String, String>::cl_type()),
+        ])
+    }
+}
+
+impl ToBytes for GrantInfo {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.terms_hash.to_bytes()?);
+        result.append(&mut self.grant_id.to_bytes()?);
+        result.append(&mut self.metadata.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.terms_hash.serialized_length()
+            + self.grant_id.serialized_length()
+            + self.metadata.serialized_length()
+    }
+}
+
+impl FromBytes for GrantInfo {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (terms_hash, bytes) = Option::<[u8; 32]>::from_bytes(bytes)?;
+        let (grant_id, bytes) = Option::<String>::from_bytes(bytes)?;
+        let (metadata, bytes) = BTreeMap::<String, String>::from_bytes(bytes)?;
+        let grant_info = GrantInfo {
+            terms_hash,
+            grant_id,
+            metadata,
+        };
+        Ok((grant_info, bytes))
+    }
+}
@@ -0,0 +1,82 @@
+//! Types shared by the vesting contract and the code that reads its state,
+//! such as the test suite or off-chain tools.
+#![no_std]
+
+extern crate alloc;
+
+/// Declares a fieldless enum stored as its `u8` discriminant, with the conversion from `u8`
+/// and the byte representation that go with it. Unknown values fail with `Formatting`.
+macro_rules! u8_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $($(#[$variant_meta:meta])* $variant:ident = $value:literal,)+
+        }
+    ) => {
+        $(#[$meta])*
+        #[repr(u8)]
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub enum $name {
+            $($(#[$variant_meta])* $variant = $value,)+
+        }
+
+        impl core::convert::TryFrom<u8> for $name {
+            type Error = casper_types::bytesrepr::Error;
+
+            fn try_from(value: u8) -> Result<Self, Self::Error> {
+                match value {
+                    $($value => Ok($name::$variant),)+
+                    _ => Err(casper_types::bytesrepr::Error::Formatting),
+                }
+            }
+        }
+
+        impl casper_types::CLTyped for $name {
+            fn cl_type() -> casper_types::CLType {
+                casper_types::CLType::U8
+            }
+        }
+
+        impl casper_types::bytesrepr::ToBytes for $name {
+            fn to_bytes(&self) -> Result<alloc::vec::Vec<u8>, casper_types::bytesrepr::Error> {
+                casper_types::bytesrepr::ToBytes::to_bytes(&(*self as u8))
+            }
+
+            fn serialized_length(&self) -> usize {
+                casper_types::bytesrepr::U8_SERIALIZED_LENGTH
+            }
+        }
+
+        impl casper_types::bytesrepr::FromBytes for $name {
+            fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), casper_types::bytesrepr::Error> {
+                let (value, bytes) = <u8 as casper_types::bytesrepr::FromBytes>::from_bytes(bytes)?;
+                Ok((core::convert::TryFrom::try_from(value)?, bytes))
+            }
+        }
+    };
+}
+
+pub mod calendar;
+mod clock;
+mod destination;
+mod event;
+mod grant;
+mod lifecycle;
+mod milestone;
+mod pause;
+mod schedule;
+mod split;
+mod start;
+mod state;
+
+pub use clock::ClockSource;
+pub use destination::ReleaseDestination;
+pub use event::VestingEvent;
+pub use grant::GrantInfo;
+pub use lifecycle::Lifecycle;
+pub use milestone::{Milestone, MilestoneStatus};
+pub use pause::PauseMode;
+pub use schedule::DripSchedule;
+pub use split::{PayoutSplit, MAX_BASIS_POINTS};
+pub use start::StartTrigger;
+pub use state::{time_from_u512, Amount, Time, VestingState, STATE_VERSION};
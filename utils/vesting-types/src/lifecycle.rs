@@ -0,0 +1,23 @@
+u8_enum! {
+    /// Where the grant stands, updated by the entry points that move it along.
+    ///
+    /// `Pending` moves to `Active` once the grant is accepted and its schedule started, and
+    /// `Active` to and from `Paused`. `Revoked` and `Completed` are final: the first when the admin
+    /// takes the grant back, the second when a finished grant is closed. Funds that reach the purse
+    /// of a revoked grant can still be sent out.
+    pub enum Lifecycle {
+        /// Waiting for the recipient's acceptance or for the start trigger.
+        Pending = 0,
+        Active = 1,
+        Paused = 2,
+        Revoked = 3,
+        Completed = 4,
+    }
+}
+
+impl Lifecycle {
+    /// Whether the grant can't change anymore.
+    pub fn is_final(self) -> bool {
+        matches!(self, Lifecycle::Revoked | Lifecycle::Completed)
+    }
+}
@@ -0,0 +1,76 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::{Amount, Time};
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLType, CLTyped,
+};
+
+u8_enum! {
+    pub enum MilestoneStatus {
+        Pending = 0,
+        /// Attested by the admin, its amount is vested.
+        Completed = 1,
+        /// Its deadline passed before completion and its amount went back to the admin.
+        Reclaimed = 2,
+    }
+}
+
+/// A tranche of the grant that vests when the admin attests its completion.
+/// Milestones are identified by their position in the list given at install.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Milestone {
+    pub name: String,
+    pub amount: Amount,
+    /// Time after which the amount can be reclaimed by the admin if the milestone is still pending.
+    pub deadline: Option<Time>,
+    pub status: MilestoneStatus,
+}
+
+impl Milestone {
+    /// Whether the amount can be reclaimed by the admin at `now`.
+    pub fn is_expired(&self, now: Time) -> bool {
+        self.status == MilestoneStatus::Pending
+            && self.deadline.map_or(false, |deadline| now >= deadline)
+    }
+}
+
+impl CLTyped for Milestone {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl ToBytes for Milestone {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.name.to_bytes()?);
+        result.append(&mut self.amount.to_bytes()?);
+        result.append(&mut self.deadline.to_bytes()?);
+        result.append(&mut self.status.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.name.serialized_length()
+            + self.amount.serialized_length()
+            + self.deadline.serialized_length()
+            + self.status.serialized_length()
+    }
+}
+
+impl FromBytes for Milestone {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (name, bytes) = String::from_bytes(bytes)?;
+        let (amount, bytes) = Amount::from_bytes(bytes)?;
+        let (deadline, bytes) = Option::<Time>::from_bytes(bytes)?;
+        let (status, bytes) = MilestoneStatus::from_bytes(bytes)?;
+        let milestone = Milestone {
+            name,
+            amount,
+            deadline,
+            status,
+        };
+        Ok((milestone, bytes))
+    }
+}
@@ -0,0 +1,16 @@
+u8_enum! {
+    /// What happens to the schedule while the contract is paused.
+    pub enum PauseMode {
+        /// The cliff and every drip are pushed back by the time on pause.
+        Shifting = 0,
+        /// The schedule keeps its dates, and what it would have vested during the pause
+        /// is forfeited.
+        Forfeiting = 1,
+    }
+}
+
+impl Default for PauseMode {
+    fn default() -> Self {
+        PauseMode::Shifting
+    }
+}
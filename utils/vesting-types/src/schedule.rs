@@ -0,0 +1,24 @@
+u8_enum! {
+    /// How often drips unlock after the cliff.
+    pub enum DripSchedule {
+        /// Every `drip_duration`, in the unit of the contract's clock.
+        Fixed = 0,
+        /// Every calendar month, on the day of month and time of day of the cliff, in UTC.
+        Monthly = 1,
+        /// Every three calendar months, on the day of month and time of day of the cliff, in UTC.
+        Quarterly = 2,
+    }
+}
+
+impl DripSchedule {
+    /// Whether drips follow the calendar, which needs the block time clock.
+    pub fn is_calendar(&self) -> bool {
+        *self != DripSchedule::Fixed
+    }
+}
+
+impl Default for DripSchedule {
+    fn default() -> Self {
+        DripSchedule::Fixed
+    }
+}
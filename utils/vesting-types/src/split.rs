@@ -0,0 +1,57 @@
+use alloc::vec::Vec;
+
+use crate::Amount;
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLType, CLTyped,
+};
+
+/// Basis points making up a whole amount.
+pub const MAX_BASIS_POINTS: u16 = 10_000;
+
+/// Part of every withdrawal sent to another account than the recipient's.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PayoutSplit {
+    pub target: AccountHash,
+    /// Share of each withdrawal, in hundredths of a percent.
+    pub basis_points: u16,
+}
+
+impl PayoutSplit {
+    /// Share of `amount` sent to the target, rounded down so the recipient keeps the remainder.
+    pub fn share(&self, amount: Amount) -> Amount {
+        amount * self.basis_points / MAX_BASIS_POINTS
+    }
+}
+
+impl CLTyped for PayoutSplit {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl ToBytes for PayoutSplit {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.target.to_bytes()?);
+        result.append(&mut self.basis_points.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.target.serialized_length() + self.basis_points.serialized_length()
+    }
+}
+
+impl FromBytes for PayoutSplit {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (target, bytes) = AccountHash::from_bytes(bytes)?;
+        let (basis_points, bytes) = u16::from_bytes(bytes)?;
+        let split = PayoutSplit {
+            target,
+            basis_points,
+        };
+        Ok((split, bytes))
+    }
+}
@@ -0,0 +1,20 @@
+u8_enum! {
+    /// What fixes the cliff of the schedule, either set at install or `cliff_offset`
+    /// after a trigger.
+    pub enum StartTrigger {
+        /// `cliff_timestamp` is given at install.
+        Absolute = 0,
+        /// The schedule starts at install.
+        Install = 1,
+        /// The schedule starts once the vesting purse holds the whole grant.
+        Funding = 2,
+        /// The schedule starts when the recipient accepts the grant.
+        Acceptance = 3,
+    }
+}
+
+impl Default for StartTrigger {
+    fn default() -> Self {
+        StartTrigger::Absolute
+    }
+}
@@ -0,0 +1,602 @@
+use alloc::vec::Vec;
+use core::cmp;
+
+use crate::{
+    calendar, ClockSource, DripSchedule, Lifecycle, PauseMode, ReleaseDestination, StartTrigger,
+    MAX_BASIS_POINTS,
+};
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
+    CLType, CLTyped, ContractHash, U512,
+};
+
+/// A point in time or a duration, in the unit of the contract's `ClockSource`:
+/// milliseconds for block time, eras for the era id.
+pub type Time = u64;
+pub type Amount = U512;
+
+/// Version of the serialized `VestingState` layout, written as its first byte.
+/// Contracts installed before it kept their state under one named key per field, which
+/// `migrate` moves into this layout.
+pub const STATE_VERSION: u8 = 1;
+
+/// The whole state of a vesting contract, stored under a single URef.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VestingState {
+    pub admin: AccountHash,
+    pub recipient: AccountHash,
+    pub cliff_timestamp: Time,
+    pub cliff_amount: Amount,
+    pub drip_duration: Time,
+    pub drip_amount: Amount,
+    pub total_amount: Amount,
+    pub released_amount: Amount,
+    pub admin_release_duration: Time,
+    pub is_paused: bool,
+    pub last_pause_timestamp: Time,
+    pub on_pause_duration: Time,
+    /// The recipient can delegate as well as the admin.
+    pub recipient_can_delegate: bool,
+    pub rewards_to_admin: bool,
+    pub delegated_amount: Amount,
+    pub unbonding_amount: Amount,
+    pub rewards_paid: Amount,
+    pub clock_source: ClockSource,
+    pub drip_schedule: DripSchedule,
+    /// Amount vested by the original schedule when the schedule was last rebased.
+    pub rebase_scheduled_amount: Amount,
+    /// Amount vested right after the schedule was last rebased.
+    pub rebase_vested_amount: Amount,
+    /// Number of milestones, kept in the contract's `milestones` dictionary under their id.
+    /// When not zero, the grant vests by milestones instead of by the cliff and drips.
+    pub milestone_count: u32,
+    /// Sum of the amounts of the completed milestones.
+    pub completed_milestones_amount: Amount,
+    /// Contract whose `can_withdraw` entry point has to allow every withdrawal.
+    pub condition_contract: Option<ContractHash>,
+    /// Where `admin_release` sends the funds, the calling admin account if not set.
+    pub release_destination: Option<ReleaseDestination>,
+    /// Destination proposed by the admin, which takes effect once confirmed.
+    pub pending_release_destination: Option<ReleaseDestination>,
+    /// Part of `total_amount` taken back by the admin before it vested. Not counted in
+    /// `released_amount`, which only counts what left the contract for the recipient
+    /// or through a full admin release.
+    pub reclaimed_amount: Amount,
+    /// Time after which the admin can sweep whatever the recipient hasn't withdrawn.
+    pub claim_deadline: Option<Time>,
+    /// Withdrawals are rejected while the contract is paused, instead of only stopping its clock.
+    pub strict_pause: bool,
+    pub pause_mode: PauseMode,
+    /// Part of `total_amount` forfeited by pauses under `PauseMode::Forfeiting`,
+    /// still in the contract until the admin reclaims it.
+    pub forfeited_amount: Amount,
+    /// The recipient has yet to accept the grant, and can't withdraw or delegate until then.
+    pub pending_acceptance: bool,
+    pub acceptance_timestamp: Option<Time>,
+    /// Hash of the agreement the recipient signed, passed on acceptance.
+    pub agreement_hash: Option<[u8; 32]>,
+    pub start_trigger: StartTrigger,
+    /// Time from the start trigger to the cliff, unused with `StartTrigger::Absolute`.
+    pub cliff_offset: Time,
+    /// When the start trigger happened and `cliff_timestamp` was fixed.
+    pub start_timestamp: Option<Time>,
+    pub lifecycle: Lifecycle,
+    /// Number of accounts that deposited through the `deposit` entry point, kept in the
+    /// contract's `funders` dictionary under the index of their first deposit. How much each
+    /// one paid is kept in the contract's `deposits` dictionary.
+    pub funder_count: u32,
+    /// Sum of the deposits recorded per funder.
+    pub deposited_amount: Amount,
+    /// Number of payout splits, kept in the contract's `payout_splits` dictionary under their
+    /// index. Each one sends a share of every withdrawal to another account, the recipient
+    /// getting the rest.
+    pub payout_split_count: u32,
+    /// Fee taken on every withdrawal, in basis points.
+    pub fee_bps: u16,
+    /// Account the fees are sent to, set along with a non-zero `fee_bps`.
+    pub fee_collector: Option<AccountHash>,
+    /// Sum of the fees sent to the fee collector.
+    pub fees_paid: Amount,
+    /// Number of calls that transferred motes in or out of the contract, the sequence number
+    /// transfer ids are derived from.
+    pub transfer_count: u64,
+    /// Id of the main transfer made by the last such call.
+    pub last_transfer_id: Option<u64>,
+}
+
+impl VestingState {
+    /// Time spent on pause so far, including the ongoing pause.
+    pub fn total_paused_duration(&self, now: Time) -> Time {
+        self.on_pause_duration
+            + if self.is_paused {
+                now - self.last_pause_timestamp
+            } else {
+                0
+            }
+    }
+
+    pub fn has_milestones(&self) -> bool {
+        self.milestone_count != 0
+    }
+
+    /// Whether the schedule is still waiting for its start trigger, in which case nothing vests.
+    pub fn is_start_pending(&self) -> bool {
+        self.start_trigger != StartTrigger::Absolute && self.start_timestamp.is_none()
+    }
+
+    /// Moves a pending grant to `Lifecycle::Active` once it is accepted and its schedule started.
+    pub fn activate_if_ready(&mut self) {
+        if self.lifecycle == Lifecycle::Pending
+            && !self.pending_acceptance
+            && !self.is_start_pending()
+        {
+            self.lifecycle = Lifecycle::Active;
+        }
+    }
+
+    /// Fixes the cliff `cliff_offset` after `now`, when the start trigger happens.
+    pub fn start(&mut self, now: Time) {
+        self.cliff_timestamp = now + self.cliff_offset;
+        self.start_timestamp = Some(now);
+    }
+
+    /// Amount vested at `now` by the schedule set at install.
+    pub fn scheduled_amount(&self, now: Time) -> Amount {
+        if self.has_milestones() {
+            return self.completed_milestones_amount;
+        }
+        if self.is_start_pending() {
+            return Amount::zero();
+        }
+        let (now, cliff_timestamp_adjusted) = match self.pause_mode {
+            PauseMode::Shifting => (now, self.cliff_timestamp + self.total_paused_duration(now)),
+            // The schedule is frozen during the pause, and resumes on its original dates.
+            PauseMode::Forfeiting if self.is_paused => {
+                (self.last_pause_timestamp, self.cliff_timestamp)
+            }
+            PauseMode::Forfeiting => (now, self.cliff_timestamp),
+        };
+        if now < cliff_timestamp_adjusted {
+            Amount::zero()
+        } else {
+            let time_diff: Time = now - cliff_timestamp_adjusted;
+            let available_drips = match self.drip_schedule {
+                DripSchedule::Fixed if self.drip_duration == 0 => 0,
+                DripSchedule::Fixed => time_diff / self.drip_duration,
+                DripSchedule::Monthly => calendar::months_between(cliff_timestamp_adjusted, now),
+                DripSchedule::Quarterly => {
+                    calendar::months_between(cliff_timestamp_adjusted, now) / 3
+                }
+            };
+            let mut counter = self.cliff_amount;
+            counter += self.drip_amount * Amount::from(available_drips);
+            cmp::min(counter, self.total_amount)
+        }
+    }
+
+    /// Time at which the schedule set at install vests the total amount, moved by the time
+    /// spent on pause so far under `PauseMode::Shifting`.
+    /// `None` for milestone grants, before the start trigger, or if the drips never get there.
+    pub fn full_vesting_time(&self, now: Time) -> Option<Time> {
+        if self.has_milestones() || self.is_start_pending() {
+            return None;
+        }
+        let cliff_timestamp_adjusted = match self.pause_mode {
+            PauseMode::Shifting => self.cliff_timestamp + self.total_paused_duration(now),
+            PauseMode::Forfeiting => self.cliff_timestamp,
+        };
+        if self.cliff_amount >= self.total_amount {
+            return Some(cliff_timestamp_adjusted);
+        }
+        if self.drip_amount.is_zero() {
+            return None;
+        }
+        let remaining_amount = self.total_amount - self.cliff_amount;
+        let drips = (remaining_amount + self.drip_amount - Amount::one()) / self.drip_amount;
+        if drips > Amount::from(u64::MAX) {
+            return None;
+        }
+        let drips = drips.as_u64();
+        match self.drip_schedule {
+            DripSchedule::Fixed if self.drip_duration == 0 => None,
+            DripSchedule::Fixed => drips
+                .checked_mul(self.drip_duration)
+                .and_then(|duration| cliff_timestamp_adjusted.checked_add(duration)),
+            DripSchedule::Monthly => Some(calendar::add_months(cliff_timestamp_adjusted, drips)),
+            DripSchedule::Quarterly => drips
+                .checked_mul(3)
+                .map(|months| calendar::add_months(cliff_timestamp_adjusted, months)),
+        }
+    }
+
+    /// `claim_deadline` moved by the time spent on pause so far under `PauseMode::Shifting`,
+    /// like the schedule, so that the recipient keeps as long to withdraw once fully vested.
+    pub fn adjusted_claim_deadline(&self, now: Time) -> Option<Time> {
+        let claim_deadline = self.claim_deadline?;
+        Some(match self.pause_mode {
+            PauseMode::Shifting => claim_deadline + self.total_paused_duration(now),
+            PauseMode::Forfeiting => claim_deadline,
+        })
+    }
+
+    /// Amount granted to the recipient, `total_amount` without the reclaimed and forfeited parts.
+    pub fn granted_amount(&self) -> Amount {
+        self.total_amount - self.reclaimed_amount - self.forfeited_amount
+    }
+
+    /// Fee on a payout of `amount`, rounded down so that it never exceeds `fee_bps`.
+    pub fn fee(&self, amount: Amount) -> Amount {
+        amount * self.fee_bps / MAX_BASIS_POINTS
+    }
+
+    /// Amount neither released nor reclaimed yet, delegated or not.
+    pub fn remaining_amount(&self) -> Amount {
+        self.total_amount - self.reclaimed_amount - self.released_amount
+    }
+
+    /// Releases whatever remains in the contract to the admin, and returns the amount.
+    /// The forfeited part is counted as reclaimed, the rest as released.
+    pub fn release_remaining(&mut self) -> Amount {
+        let amount = self.remaining_amount();
+        self.released_amount = self.granted_amount();
+        self.reclaimed_amount += self.forfeited_amount;
+        self.forfeited_amount = Amount::zero();
+        amount
+    }
+
+    /// Ends the pause at `now`. Under `PauseMode::Forfeiting`, what the schedule would have
+    /// vested during the pause is forfeited, and the forfeited amount is returned.
+    pub fn end_pause(&mut self, now: Time) -> Amount {
+        let paused_vested_amount = self.vested_amount(now);
+        self.on_pause_duration += now - self.last_pause_timestamp;
+        self.is_paused = false;
+        if self.pause_mode != PauseMode::Forfeiting {
+            return Amount::zero();
+        }
+        let forfeited_amount = self.vested_amount(now) - paused_vested_amount;
+        if !forfeited_amount.is_zero() {
+            self.rebase(now, paused_vested_amount);
+            self.forfeited_amount += forfeited_amount;
+        }
+        forfeited_amount
+    }
+
+    /// Amount vested at `now`, released or not.
+    ///
+    /// Once the schedule has been rebased, the rest of it is scaled so that it vests the whole
+    /// granted amount at its original end: what the original schedule vests after the rebase
+    /// is shared out over the unvested balance left by the rebase.
+    pub fn vested_amount(&self, now: Time) -> Amount {
+        let scheduled = self.scheduled_amount(now);
+        // Reclaimed milestones are never completed, so they are never scheduled.
+        if self.has_milestones() {
+            return scheduled;
+        }
+        let granted = self.granted_amount();
+        if scheduled >= self.total_amount {
+            return granted;
+        }
+        self.rebase_vested_amount
+            + (scheduled - self.rebase_scheduled_amount) * (granted - self.rebase_vested_amount)
+                / (self.total_amount - self.rebase_scheduled_amount)
+    }
+
+    /// Amount the recipient is eligible to withdraw at `now`.
+    pub fn available_amount(&self, now: Time) -> Amount {
+        self.vested_amount(now) - self.released_amount
+    }
+
+    /// Makes the vested amount at `now` jump to `vested_amount`, and the rest of the schedule
+    /// vest the remaining balance.
+    pub fn rebase(&mut self, now: Time, vested_amount: Amount) {
+        self.rebase_scheduled_amount = self.scheduled_amount(now);
+        self.rebase_vested_amount = vested_amount;
+    }
+
+    /// Motes that are still expected to sit in the vesting purse,
+    /// i.e. neither released nor reclaimed yet, and neither bonded nor unbonding.
+    pub fn liquid_amount(&self) -> Amount {
+        self.remaining_amount() - self.delegated_amount - self.unbonding_amount
+    }
+
+    /// Part of the balance still unvested at `now` that isn't bonded or unbonding yet,
+    /// the most that can be delegated without bonding funds the recipient can withdraw.
+    pub fn delegable_amount(&self, now: Time) -> Amount {
+        let unvested_amount = self.granted_amount() - self.vested_amount(now);
+        unvested_amount.saturating_sub(self.delegated_amount + self.unbonding_amount)
+    }
+}
+
+impl CLTyped for VestingState {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl ToBytes for VestingState {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut STATE_VERSION.to_bytes()?);
+        result.append(&mut self.admin.to_bytes()?);
+        result.append(&mut self.recipient.to_bytes()?);
+        result.append(&mut self.cliff_timestamp.to_bytes()?);
+        result.append(&mut self.cliff_amount.to_bytes()?);
+        result.append(&mut self.drip_duration.to_bytes()?);
+        result.append(&mut self.drip_amount.to_bytes()?);
+        result.append(&mut self.total_amount.to_bytes()?);
+        result.append(&mut self.released_amount.to_bytes()?);
+        result.append(&mut self.admin_release_duration.to_bytes()?);
+        result.append(&mut self.is_paused.to_bytes()?);
+        result.append(&mut self.last_pause_timestamp.to_bytes()?);
+        result.append(&mut self.on_pause_duration.to_bytes()?);
+        result.append(&mut self.recipient_can_delegate.to_bytes()?);
+        result.append(&mut self.rewards_to_admin.to_bytes()?);
+        result.append(&mut self.delegated_amount.to_bytes()?);
+        result.append(&mut self.unbonding_amount.to_bytes()?);
+        result.append(&mut self.rewards_paid.to_bytes()?);
+        result.append(&mut self.clock_source.to_bytes()?);
+        result.append(&mut self.drip_schedule.to_bytes()?);
+        result.append(&mut self.rebase_scheduled_amount.to_bytes()?);
+        result.append(&mut self.rebase_vested_amount.to_bytes()?);
+        result.append(&mut self.milestone_count.to_bytes()?);
+        result.append(&mut self.completed_milestones_amount.to_bytes()?);
+        result.append(&mut self.condition_contract.to_bytes()?);
+        result.append(&mut self.release_destination.to_bytes()?);
+        result.append(&mut self.pending_release_destination.to_bytes()?);
+        result.append(&mut self.reclaimed_amount.to_bytes()?);
+        result.append(&mut self.claim_deadline.to_bytes()?);
+        result.append(&mut self.strict_pause.to_bytes()?);
+        result.append(&mut self.pause_mode.to_bytes()?);
+        result.append(&mut self.forfeited_amount.to_bytes()?);
+        result.append(&mut self.pending_acceptance.to_bytes()?);
+        result.append(&mut self.acceptance_timestamp.to_bytes()?);
+        result.append(&mut self.agreement_hash.to_bytes()?);
+        result.append(&mut self.start_trigger.to_bytes()?);
+        result.append(&mut self.cliff_offset.to_bytes()?);
+        result.append(&mut self.start_timestamp.to_bytes()?);
+        result.append(&mut self.lifecycle.to_bytes()?);
+        result.append(&mut self.funder_count.to_bytes()?);
+        result.append(&mut self.deposited_amount.to_bytes()?);
+        result.append(&mut self.payout_split_count.to_bytes()?);
+        result.append(&mut self.fee_bps.to_bytes()?);
+        result.append(&mut self.fee_collector.to_bytes()?);
+        result.append(&mut self.fees_paid.to_bytes()?);
+        result.append(&mut self.transfer_count.to_bytes()?);
+        result.append(&mut self.last_transfer_id.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+            + self.admin.serialized_length()
+            + self.recipient.serialized_length()
+            + self.cliff_timestamp.serialized_length()
+            + self.cliff_amount.serialized_length()
+            + self.drip_duration.serialized_length()
+            + self.drip_amount.serialized_length()
+            + self.total_amount.serialized_length()
+            + self.released_amount.serialized_length()
+            + self.admin_release_duration.serialized_length()
+            + self.is_paused.serialized_length()
+            + self.last_pause_timestamp.serialized_length()
+            + self.on_pause_duration.serialized_length()
+            + self.recipient_can_delegate.serialized_length()
+            + self.rewards_to_admin.serialized_length()
+            + self.delegated_amount.serialized_length()
+            + self.unbonding_amount.serialized_length()
+            + self.rewards_paid.serialized_length()
+            + self.clock_source.serialized_length()
+            + self.drip_schedule.serialized_length()
+            + self.rebase_scheduled_amount.serialized_length()
+            + self.rebase_vested_amount.serialized_length()
+            + self.milestone_count.serialized_length()
+            + self.completed_milestones_amount.serialized_length()
+            + self.condition_contract.serialized_length()
+            + self.release_destination.serialized_length()
+            + self.pending_release_destination.serialized_length()
+            + self.reclaimed_amount.serialized_length()
+            + self.claim_deadline.serialized_length()
+            + self.strict_pause.serialized_length()
+            + self.pause_mode.serialized_length()
+            + self.forfeited_amount.serialized_length()
+            + self.pending_acceptance.serialized_length()
+            + self.acceptance_timestamp.serialized_length()
+            + self.agreement_hash.serialized_length()
+            + self.start_trigger.serialized_length()
+            + self.cliff_offset.serialized_length()
+            + self.start_timestamp.serialized_length()
+            + self.lifecycle.serialized_length()
+            + self.funder_count.serialized_length()
+            + self.deposited_amount.serialized_length()
+            + self.payout_split_count.serialized_length()
+            + self.fee_bps.serialized_length()
+            + self.fee_collector.serialized_length()
+            + self.fees_paid.serialized_length()
+            + self.transfer_count.serialized_length()
+            + self.last_transfer_id.serialized_length()
+    }
+}
+
+impl FromBytes for VestingState {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (version, bytes) = u8::from_bytes(bytes)?;
+        if version != STATE_VERSION {
+            return Err(bytesrepr::Error::Formatting);
+        }
+        let (admin, bytes) = AccountHash::from_bytes(bytes)?;
+        let (recipient, bytes) = AccountHash::from_bytes(bytes)?;
+        let (cliff_timestamp, bytes) = Time::from_bytes(bytes)?;
+        let (cliff_amount, bytes) = Amount::from_bytes(bytes)?;
+        let (drip_duration, bytes) = Time::from_bytes(bytes)?;
+        let (drip_amount, bytes) = Amount::from_bytes(bytes)?;
+        let (total_amount, bytes) = Amount::from_bytes(bytes)?;
+        let (released_amount, bytes) = Amount::from_bytes(bytes)?;
+        let (admin_release_duration, bytes) = Time::from_bytes(bytes)?;
+        let (is_paused, bytes) = bool::from_bytes(bytes)?;
+        let (last_pause_timestamp, bytes) = Time::from_bytes(bytes)?;
+        let (on_pause_duration, bytes) = Time::from_bytes(bytes)?;
+        let (recipient_can_delegate, bytes) = bool::from_bytes(bytes)?;
+        let (rewards_to_admin, bytes) = bool::from_bytes(bytes)?;
+        let (delegated_amount, bytes) = Amount::from_bytes(bytes)?;
+        let (unbonding_amount, bytes) = Amount::from_bytes(bytes)?;
+        let (rewards_paid, bytes) = Amount::from_bytes(bytes)?;
+        let (clock_source, bytes) = ClockSource::from_bytes(bytes)?;
+        let (drip_schedule, bytes) = DripSchedule::from_bytes(bytes)?;
+        let (rebase_scheduled_amount, bytes) = Amount::from_bytes(bytes)?;
+        let (rebase_vested_amount, bytes) = Amount::from_bytes(bytes)?;
+        let (milestone_count, bytes) = u32::from_bytes(bytes)?;
+        let (completed_milestones_amount, bytes) = Amount::from_bytes(bytes)?;
+        let (condition_contract, bytes) = Option::<ContractHash>::from_bytes(bytes)?;
+        let (release_destination, bytes) = Option::<ReleaseDestination>::from_bytes(bytes)?;
+        let (pending_release_destination, bytes) = Option::<ReleaseDestination>::from_bytes(bytes)?;
+        let (reclaimed_amount, bytes) = Amount::from_bytes(bytes)?;
+        let (claim_deadline, bytes) = Option::<Time>::from_bytes(bytes)?;
+        let (strict_pause, bytes) = bool::from_bytes(bytes)?;
+        let (pause_mode, bytes) = PauseMode::from_bytes(bytes)?;
+        let (forfeited_amount, bytes) = Amount::from_bytes(bytes)?;
+        let (pending_acceptance, bytes) = bool::from_bytes(bytes)?;
+        let (acceptance_timestamp, bytes) = Option::<Time>::from_bytes(bytes)?;
+        let (agreement_hash, bytes) = Option::<[u8; 32]>::from_bytes(bytes)?;
+        let (start_trigger, bytes) = StartTrigger::from_bytes(bytes)?;
+        let (cliff_offset, bytes) = Time::from_bytes(bytes)?;
+        let (start_timestamp, bytes) = Option::<Time>::from_bytes(bytes)?;
+        let (lifecycle, bytes) = Lifecycle::from_bytes(bytes)?;
+        let (funder_count, bytes) = u32::from_bytes(bytes)?;
+        let (deposited_amount, bytes) = Amount::from_bytes(bytes)?;
+        let (payout_split_count, bytes) = u32::from_bytes(bytes)?;
+        let (fee_bps, bytes) = u16::from_bytes(bytes)?;
+        let (fee_collector, bytes) = Option::<AccountHash>::from_bytes(bytes)?;
+        let (fees_paid, bytes) = Amount::from_bytes(bytes)?;
+        let (transfer_count, bytes) = u64::from_bytes(bytes)?;
+        let (last_transfer_id, bytes) = Option::<u64>::from_bytes(bytes)?;
+        let state = VestingState {
+            admin,
+            recipient,
+            cliff_timestamp,
+            cliff_amount,
+            drip_duration,
+            drip_amount,
+            total_amount,
+            released_amount,
+            admin_release_duration,
+            is_paused,
+            last_pause_timestamp,
+            on_pause_duration,
+            recipient_can_delegate,
+            rewards_to_admin,
+            delegated_amount,
+            unbonding_amount,
+            rewards_paid,
+            clock_source,
+            drip_schedule,
+            rebase_scheduled_amount,
+            rebase_vested_amount,
+            milestone_count,
+            completed_milestones_amount,
+            condition_contract,
+            release_destination,
+            pending_release_destination,
+            reclaimed_amount,
+            claim_deadline,
+            strict_pause,
+            pause_mode,
+            forfeited_amount,
+            pending_acceptance,
+            acceptance_timestamp,
+            agreement_hash,
+            start_trigger,
+            cliff_offset,
+            start_timestamp,
+            lifecycle,
+            funder_count,
+            deposited_amount,
+            payout_split_count,
+            fee_bps,
+            fee_collector,
+            fees_paid,
+            transfer_count,
+            last_transfer_id,
+        };
+        Ok((state, bytes))
+    }
+}
+
+/// Converts a time stored in the `U512` layout, failing if it doesn't fit in a `u64`.
+pub fn time_from_u512(time: U512) -> Result<Time, bytesrepr::Error> {
+    if time > U512::from(Time::MAX) {
+        return Err(bytesrepr::Error::Formatting);
+    }
+    Ok(time.as_u64())
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::{AccessRights, URef};
+
+    use super::*;
+
+    fn sample_state() -> VestingState {
+        VestingState {
+            admin: AccountHash::new([1u8; 32]),
+            recipient: AccountHash::new([2u8; 32]),
+            cliff_timestamp: 1_000,
+            cliff_amount: 100.into(),
+            drip_duration: 10,
+            drip_amount: 5.into(),
+            total_amount: 1_000.into(),
+            released_amount: 50.into(),
+            admin_release_duration: 500,
+            is_paused: true,
+            last_pause_timestamp: 1_200,
+            on_pause_duration: 30,
+            recipient_can_delegate: true,
+            rewards_to_admin: true,
+            delegated_amount: 200.into(),
+            unbonding_amount: 20.into(),
+            rewards_paid: 3.into(),
+            clock_source: ClockSource::EraId,
+            drip_schedule: DripSchedule::Quarterly,
+            rebase_scheduled_amount: 150.into(),
+            rebase_vested_amount: 160.into(),
+            milestone_count: 2,
+            completed_milestones_amount: 70.into(),
+            condition_contract: Some(ContractHash::new([3u8; 32])),
+            release_destination: Some(ReleaseDestination::Account(AccountHash::new([4u8; 32]))),
+            pending_release_destination: Some(ReleaseDestination::Purse(URef::new(
+                [5u8; 32],
+                AccessRights::ADD,
+            ))),
+            reclaimed_amount: 40.into(),
+            claim_deadline: Some(9_000),
+            strict_pause: true,
+            pause_mode: PauseMode::Forfeiting,
+            forfeited_amount: 8.into(),
+            pending_acceptance: true,
+            acceptance_timestamp: Some(900),
+            agreement_hash: Some([6u8; 32]),
+            start_trigger: StartTrigger::Acceptance,
+            cliff_offset: 100,
+            start_timestamp: Some(900),
+            lifecycle: Lifecycle::Paused,
+            funder_count: 2,
+            deposited_amount: 1_000.into(),
+            payout_split_count: 1,
+            fee_bps: 250,
+            fee_collector: Some(AccountHash::new([7u8; 32])),
+            fees_paid: 12.into(),
+            transfer_count: 4,
+            last_transfer_id: Some(42),
+        }
+    }
+
+    #[test]
+    fn state_bytes_round_trip() {
+        let state = sample_state();
+        let bytes = state.to_bytes().unwrap();
+        assert_eq!(bytes.len(), state.serialized_length());
+        let (decoded, rest) = VestingState::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded, state);
+        assert!(rest.is_empty());
+    }
+}